@@ -19,7 +19,7 @@ fn main() {
     assert_eq!(Some(&(33..44)), parsed.header_span("x-key"));
     assert_eq!(Some("x-key: 123\n"), parsed.header_str("x-key"));
 
-    let request: HttpRequest = parsed.into();
+    let request: HttpRequest = parsed.try_into().unwrap();
 
     assert_eq!(
         HttpRequest {
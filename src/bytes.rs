@@ -0,0 +1,57 @@
+/// Split a raw message at the first blank line into the head and body bytes
+///
+/// The head is the start line and header lines including their line endings. The
+/// blank `\r\n` or `\n` separator line is excluded from both. The body isn't required
+/// to be valid UTF-8. A message without a blank line is all head with an empty body.
+pub fn split_message(input: &[u8]) -> (&[u8], &[u8]) {
+    let mut line_start = 0;
+
+    while let Some(newline) = input[line_start..]
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .map(|idx| line_start + idx)
+    {
+        let line = &input[line_start..newline];
+
+        if line.is_empty() || line == b"\r" {
+            return (&input[..line_start], &input[newline + 1..]);
+        }
+
+        line_start = newline + 1;
+    }
+
+    (input, &[])
+}
+
+#[cfg(test)]
+mod split_message_tests {
+    use super::*;
+
+    #[test]
+    fn test_split_message_lf() {
+        let (head, body) = split_message(b"GET / HTTP/1.1\nx-key: 123\n\nbody\n");
+        assert_eq!(head, b"GET / HTTP/1.1\nx-key: 123\n");
+        assert_eq!(body, b"body\n");
+    }
+
+    #[test]
+    fn test_split_message_crlf() {
+        let (head, body) = split_message(b"GET / HTTP/1.1\r\nx-key: 123\r\n\r\nbody");
+        assert_eq!(head, b"GET / HTTP/1.1\r\nx-key: 123\r\n");
+        assert_eq!(body, b"body");
+    }
+
+    #[test]
+    fn test_split_message_binary_body() {
+        let (head, body) = split_message(b"POST / HTTP/1.1\r\n\r\n\xff\x00\n\n\xfe");
+        assert_eq!(head, b"POST / HTTP/1.1\r\n");
+        assert_eq!(body, b"\xff\x00\n\n\xfe");
+    }
+
+    #[test]
+    fn test_split_message_without_separator() {
+        let (head, body) = split_message(b"GET / HTTP/1.1\nx-key: 123");
+        assert_eq!(head, b"GET / HTTP/1.1\nx-key: 123");
+        assert_eq!(body, b"");
+    }
+}
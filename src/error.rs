@@ -1,11 +1,50 @@
 use snafu::prelude::*;
 
+use crate::span::{Span, offset_to_line_col};
+
 #[derive(Debug, Snafu, PartialEq)]
 pub enum Error {
     #[snafu(display("HTTP Message strings can't be empty"))]
     EmptyHttpMessage,
     #[snafu(display("Required but not found: {key}"))]
     MissingRequired { key: String },
+    #[snafu(display("Invalid status code: {status_code}"))]
+    InvalidStatusCode { status_code: String },
+    #[snafu(display("Unresolved template variable: {name}"))]
+    UnresolvedTemplateVariable { name: String, span: Span },
+    #[snafu(display("Header found after the body separator at {span:?}"))]
+    HeaderAfterBody { span: Span },
+    #[snafu(display("Invalid uri: {uri}"))]
+    InvalidUri { uri: String },
+    #[snafu(display("Invalid header: {key}"))]
+    InvalidHeader { key: String },
+    #[snafu(display("Unsupported HTTP version: {http_version}"))]
+    UnsupportedHttpVersion { http_version: String },
+    #[snafu(display("Body isn't valid UTF-8"))]
+    InvalidBody,
+    #[snafu(display("Body couldn't be deserialized: {message}"))]
+    BodyDeserialization { message: String },
+    #[snafu(display("IO error ({kind:?}): {message}"))]
+    Io {
+        kind: std::io::ErrorKind,
+        message: String,
+    },
+    #[snafu(display("Parse limit exceeded: {which}"))]
+    LimitExceeded { which: &'static str },
+    #[snafu(display("Body length {actual} doesn't match Content-Length {declared}"))]
+    BodyLengthMismatch { declared: usize, actual: usize },
+    #[snafu(display("Body couldn't be decoded as {charset}"))]
+    InvalidEncoding { charset: String },
+    #[snafu(display("Unsupported content encoding: {encoding}"))]
+    UnsupportedEncoding { encoding: String },
+    #[snafu(display("Method not allowed: {method}"))]
+    MethodNotAllowed { method: String, span: Span },
+    #[snafu(display("HTTP/1.1 requests require a Host header"))]
+    MissingHost,
+    #[snafu(display("Stream ended after {actual} of {expected} body bytes"))]
+    UnexpectedEof { expected: usize, actual: usize },
+    #[snafu(display("Unsupported transfer encoding: {encoding}"))]
+    UnsupportedTransferEncoding { encoding: String },
 }
 
 impl Error {
@@ -14,4 +53,105 @@ impl Error {
             key: key.to_string(),
         }
     }
+
+    pub fn invalid_header(key: &str) -> Self {
+        Self::InvalidHeader {
+            key: key.to_string(),
+        }
+    }
+
+    pub fn invalid_status_code(status_code: &str) -> Self {
+        Self::InvalidStatusCode {
+            status_code: status_code.to_string(),
+        }
+    }
+
+    /// Get the span in the source message the error points to, if known
+    pub fn span(&self) -> Option<&Span> {
+        match self {
+            Self::UnresolvedTemplateVariable { span, .. }
+            | Self::HeaderAfterBody { span }
+            | Self::MethodNotAllowed { span, .. } => Some(span),
+            _ => None,
+        }
+    }
+
+    /// Render the error with the source line it points to and `^` markers under the span
+    ///
+    /// Errors without a span render as their message. Spans covering multiple lines
+    /// are marked to the end of their first line.
+    pub fn render(&self, source: &str) -> String {
+        let Some(span) = self.span() else {
+            return format!("error: {self}");
+        };
+
+        let (line, column) = offset_to_line_col(source, span.start);
+        let line_text = source.lines().nth(line).unwrap_or_default();
+
+        let marked = line_text
+            .chars()
+            .skip(column)
+            .take(
+                source
+                    .get(span.clone())
+                    .map_or(0, |text| text.chars().count()),
+            )
+            .take_while(|c| *c != '\n')
+            .count()
+            .max(1);
+
+        let line_number = (line + 1).to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        format!(
+            "error: {self}\n\
+             {gutter}--> {line_number}:{}\n\
+             {gutter} |\n\
+             {line_number} | {line_text}\n\
+             {gutter} | {}{}",
+            column + 1,
+            " ".repeat(column),
+            "^".repeat(marked)
+        )
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io {
+            kind: error.kind(),
+            message: error.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let source = "GET https://example.com HTTP/1.1\nx-key: {{name}}\n\n";
+        let error = Error::UnresolvedTemplateVariable {
+            name: "name".to_string(),
+            span: 40..48,
+        };
+
+        assert_eq!(
+            error.render(source),
+            "error: Unresolved template variable: name\n \
+             --> 2:8\n  \
+             |\n\
+             2 | x-key: {{name}}\n  \
+             |        ^^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn test_render_without_span() {
+        assert_eq!(
+            Error::EmptyHttpMessage.render(""),
+            "error: HTTP Message strings can't be empty"
+        );
+    }
 }
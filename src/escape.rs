@@ -0,0 +1,42 @@
+/// Escape control characters so untrusted text is safe to print to a terminal
+///
+/// ASCII control characters become `\xNN` and other control characters become
+/// `\u{NNNN}`. Tabs, newlines, and the `\r` in `\r\n` are kept as is.
+pub(crate) fn escape_control_chars(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\t' | '\n' => escaped.push(c),
+            '\r' if chars.peek() == Some(&'\n') => escaped.push(c),
+            c if c.is_ascii_control() => escaped.push_str(&format!("\\x{:02x}", c as u32)),
+            c if c.is_control() => escaped.push_str(&format!("\\u{{{:04x}}}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod escape_control_chars_tests {
+    use super::*;
+
+    #[test]
+    fn test_leaves_text_intact() {
+        assert_eq!(
+            escape_control_chars("GET / HTTP/1.1\r\n\tkey: välue\n"),
+            "GET / HTTP/1.1\r\n\tkey: välue\n"
+        );
+    }
+
+    #[test]
+    fn test_escapes_control_chars() {
+        assert_eq!(
+            escape_control_chars("\u{1b}[31mred\u{7}\r\u{0}"),
+            "\\x1b[31mred\\x07\\x0d\\x00"
+        );
+        assert_eq!(escape_control_chars("a\u{85}b"), "a\\u{0085}b");
+    }
+}
@@ -0,0 +1,268 @@
+use crate::{
+    models::looks_like_uri,
+    span::{Span, get_byte_line_spans, trim_byte_line_ending},
+};
+
+/// The spans recovered from arbitrary bytes by [crate::parse_lenient]
+///
+/// Spans are byte ranges in to the input and are always in bounds.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PartialParseResult {
+    pub method: Option<Span>,
+    pub uri: Option<Span>,
+    pub http_version: Option<Span>,
+    pub headers: Vec<Span>,
+    pub body: Option<Span>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl PartialParseResult {
+    /// Get all recovered part and diagnostic spans
+    pub fn spans(&self) -> Vec<Span> {
+        [&self.method, &self.uri, &self.http_version, &self.body]
+            .into_iter()
+            .flatten()
+            .chain(self.headers.iter())
+            .chain(self.diagnostics.iter().map(|diagnostic| &diagnostic.span))
+            .cloned()
+            .collect()
+    }
+}
+
+/// A problem found while leniently parsing a message
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub span: Span,
+    pub kind: DiagnosticKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiagnosticKind {
+    /// The message is empty or only whitespace
+    EmptyMessage,
+    /// A byte sequence isn't valid UTF-8
+    InvalidUtf8,
+    /// The request line has no method
+    MissingMethod,
+    /// The request line has no uri
+    MissingUri,
+    /// The request line has no http version
+    MissingHttpVersion,
+    /// A header line has no `:` separating the key and value
+    HeaderWithoutColon,
+    /// There is no blank line after the headers
+    MissingSeparator,
+}
+
+impl Diagnostic {
+    fn new(span: Span, kind: DiagnosticKind) -> Self {
+        Self { span, kind }
+    }
+}
+
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Parse arbitrary bytes in to whatever request spans can be recovered
+///
+/// This never panics. Problems are returned as diagnostics instead of errors.
+pub(crate) fn parse_lenient(input: &[u8]) -> PartialParseResult {
+    let mut result = PartialParseResult {
+        diagnostics: find_invalid_utf8(input),
+        ..Default::default()
+    };
+
+    let bom_len = if input.starts_with(BOM) { BOM.len() } else { 0 };
+
+    if input[bom_len..].iter().all(u8::is_ascii_whitespace) {
+        result.diagnostics.push(Diagnostic::new(
+            bom_len..input.len(),
+            DiagnosticKind::EmptyMessage,
+        ));
+
+        return result;
+    }
+
+    let lines = get_byte_line_spans(input);
+
+    let separator_idx = lines
+        .iter()
+        .position(|line| matches!(&input[line.clone()], b"\n" | b"\r\n"));
+
+    let head_end = separator_idx.unwrap_or(lines.len());
+    let mut head_lines = lines[..head_end].iter().cloned();
+
+    if let Some(request_line) = head_lines.next() {
+        let request_line =
+            trim_byte_line_ending(input, &(bom_len.min(request_line.end)..request_line.end));
+
+        parse_request_line(input, request_line, &mut result);
+    }
+
+    for line in head_lines {
+        let content = trim_byte_line_ending(input, &line);
+
+        if !input[content.clone()].contains(&b':') {
+            result
+                .diagnostics
+                .push(Diagnostic::new(content, DiagnosticKind::HeaderWithoutColon));
+        }
+
+        result.headers.push(line);
+    }
+
+    match separator_idx.and_then(|idx| lines.get(idx)) {
+        Some(separator) => {
+            let body = separator.end..input.len();
+            result.body = (!body.is_empty()).then_some(body);
+        }
+        None => result.diagnostics.push(Diagnostic::new(
+            input.len()..input.len(),
+            DiagnosticKind::MissingSeparator,
+        )),
+    }
+
+    result
+        .diagnostics
+        .sort_by_key(|diagnostic| diagnostic.span.start);
+
+    result
+}
+
+/// Split the request line by ASCII whitespace in to method, uri, and http version
+fn parse_request_line(input: &[u8], line: Span, result: &mut PartialParseResult) {
+    let mut tokens = vec![];
+    let mut token_start = None;
+
+    for idx in line.clone() {
+        match (input[idx].is_ascii_whitespace(), token_start) {
+            (true, Some(start)) => {
+                tokens.push(start..idx);
+                token_start = None;
+            }
+            (false, None) => token_start = Some(idx),
+            _ => {}
+        }
+    }
+
+    if let Some(start) = token_start {
+        tokens.push(start..line.end);
+    }
+
+    let mut tokens = tokens.into_iter();
+
+    let lone_uri = match tokens.as_slice() {
+        [token] => looks_like_uri(&String::from_utf8_lossy(&input[token.clone()])),
+        _ => false,
+    };
+
+    if !lone_uri {
+        result.method = tokens.next();
+    }
+    result.uri = tokens.next();
+    result.http_version = tokens.next();
+
+    if result.method.is_none() {
+        result.diagnostics.push(Diagnostic::new(
+            line.start..line.start,
+            DiagnosticKind::MissingMethod,
+        ));
+    }
+
+    if result.uri.is_none() {
+        result.diagnostics.push(Diagnostic::new(
+            line.end..line.end,
+            DiagnosticKind::MissingUri,
+        ));
+    }
+
+    if result.http_version.is_none() {
+        result.diagnostics.push(Diagnostic::new(
+            line.end..line.end,
+            DiagnosticKind::MissingHttpVersion,
+        ));
+    }
+}
+
+/// Get the spans of every invalid UTF-8 byte sequence
+fn find_invalid_utf8(input: &[u8]) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let mut offset = 0;
+
+    for chunk in input.utf8_chunks() {
+        offset += chunk.valid().len();
+
+        let invalid_len = chunk.invalid().len();
+
+        if invalid_len > 0 {
+            diagnostics.push(Diagnostic::new(
+                offset..offset + invalid_len,
+                DiagnosticKind::InvalidUtf8,
+            ));
+        }
+
+        offset += invalid_len;
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod parse_lenient_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lenient_request() {
+        let result = parse_lenient(b"GET https://example.com HTTP/1.1\r\nx-key: 123\r\n\r\nbody");
+
+        assert_eq!(
+            result,
+            PartialParseResult {
+                method: Some(0..3),
+                uri: Some(4..23),
+                http_version: Some(24..32),
+                headers: vec![34..46],
+                body: Some(48..52),
+                diagnostics: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_lenient_empty() {
+        let result = parse_lenient(b"\xef\xbb\xbf \r\n");
+
+        assert_eq!(
+            result.diagnostics,
+            vec![Diagnostic::new(3..6, DiagnosticKind::EmptyMessage)]
+        );
+    }
+
+    #[test]
+    fn test_parse_lenient_diagnostics() {
+        let result = parse_lenient(b"/path\nx-key \xff\n");
+
+        assert_eq!(result.method, None);
+        assert_eq!(result.uri, Some(0..5));
+        assert_eq!(result.headers, vec![6..14]);
+        assert_eq!(
+            result.diagnostics,
+            vec![
+                Diagnostic::new(0..0, DiagnosticKind::MissingMethod),
+                Diagnostic::new(5..5, DiagnosticKind::MissingHttpVersion),
+                Diagnostic::new(6..13, DiagnosticKind::HeaderWithoutColon),
+                Diagnostic::new(12..13, DiagnosticKind::InvalidUtf8),
+                Diagnostic::new(14..14, DiagnosticKind::MissingSeparator),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_lenient_non_ascii_whitespace() {
+        let result = parse_lenient("GET\u{a0}/ HTTP/1.1\n\n".as_bytes());
+
+        assert_eq!(result.method, Some(0..6));
+        assert_eq!(result.uri, Some(7..15));
+        assert_eq!(result.http_version, None);
+    }
+}
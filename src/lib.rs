@@ -1,8 +1,29 @@
-use crate::models::{ParsedHttpRequest, PartialHttpRequest};
+use crate::models::{
+    HttpHeader, ParsedHttpRequest, ParsedHttpResponse, PartialHttpRequest, PartialHttpResponse,
+};
 
+pub mod bytes;
 pub mod error;
+mod escape;
+pub mod lenient;
+mod macros;
 pub mod models;
 pub mod span;
+mod template;
+
+/// Parse arbitrary bytes in to whatever request spans can be recovered without panicking
+///
+/// Intended for fuzzing and untrusted input. Problems are reported as diagnostics.
+pub fn parse_lenient(input: &[u8]) -> lenient::PartialParseResult {
+    lenient::parse_lenient(input)
+}
+
+/// Parse a block of `key: value` lines in to headers, unfolding continuation lines
+///
+/// Useful for headers from another source or trailers. Invalid lines are skipped.
+pub fn parse_headers(input: &str) -> Vec<HttpHeader> {
+    models::parse_headers(input)
+}
 
 /// Parse a partial HTTP request message string in to [PartialHttpRequest]
 pub fn parse_partial_request(input: &str) -> Result<PartialHttpRequest<'_>, error::Error> {
@@ -13,3 +34,18 @@ pub fn parse_partial_request(input: &str) -> Result<PartialHttpRequest<'_>, erro
 pub fn parse_request(input: &str) -> Result<ParsedHttpRequest<'_>, error::Error> {
     ParsedHttpRequest::parse(input)
 }
+
+/// Parse a partial HTTP response message string in to [PartialHttpResponse]
+pub fn parse_partial_response(input: &str) -> Result<PartialHttpResponse<'_>, error::Error> {
+    PartialHttpResponse::parse(input)
+}
+
+/// Parse the first spec compliant HTTP request in input with the number of bytes it occupies
+pub fn parse_prefix(input: &str) -> Result<(ParsedHttpRequest<'_>, usize), error::Error> {
+    ParsedHttpRequest::parse_prefix(input)
+}
+
+/// Parse a spec compliant HTTP response message string in to [ParsedHttpResponse]
+pub fn parse_response(input: &str) -> Result<ParsedHttpResponse<'_>, error::Error> {
+    ParsedHttpResponse::parse(input)
+}
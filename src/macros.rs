@@ -0,0 +1,64 @@
+/// Build an [HttpRequest](crate::models::HttpRequest) from a method, uri, headers, and body
+///
+/// Headers and the body are optional.
+///
+/// ```
+/// use http_message::request;
+///
+/// let request = request!(POST "https://example.com" {
+///     "Content-Type": "application/json",
+/// } body: "{}");
+///
+/// assert_eq!(request.body, Some("{}".to_string()));
+/// ```
+#[macro_export]
+macro_rules! request {
+    (
+        $method:ident $uri:literal
+        $({ $($key:literal : $value:expr),* $(,)? })?
+        $(body: $body:expr)?
+    ) => {
+        $crate::models::HttpRequest {
+            uri: $crate::models::Uri::new($uri),
+            method: $crate::models::HttpMethod::from(stringify!($method)),
+            http_version: ::core::default::Default::default(),
+            headers: vec![$($($crate::models::HttpHeader::new($key, &$value)),*)?],
+            body: None $(.or(Some(::std::string::String::from($body))))?,
+        }
+    };
+}
+
+#[cfg(test)]
+mod request_macro_tests {
+    use crate::models::{HttpMethod, HttpRequest};
+
+    #[test]
+    fn test_request_macro() {
+        let request = request!(POST "https://example.com" {
+            "Content-Type": "application/json",
+            "X-Id": 1.to_string()
+        } body: "{}");
+
+        assert_eq!(
+            request,
+            HttpRequest::post(
+                "https://example.com",
+                vec!["Content-Type: application/json".into(), "X-Id: 1".into()],
+                Some("{}".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn test_request_macro_without_headers_or_body() {
+        let request = request!(DELETE "https://example.com/1");
+
+        assert_eq!(request.method, HttpMethod::DELETE);
+        assert!(request.headers.is_empty());
+        assert_eq!(request.body, None);
+
+        let request = request!(GET "https://example.com" {});
+
+        assert_eq!(request, HttpRequest::get("https://example.com", vec![]));
+    }
+}
@@ -12,11 +12,10 @@ fn main() {
     }
 
     match fs::read_to_string(&args[1]) {
-        Ok(contents) => {
-            let partial = PartialHttpRequest::parse(&contents);
-
-            println!("{partial:#?}");
-        }
+        Ok(contents) => match PartialHttpRequest::parse(&contents) {
+            Ok(partial) => println!("{partial:#?}"),
+            Err(error) => eprintln!("{}", error.render(&contents)),
+        },
         Err(e) => eprintln!("Error reading file {}: {}", args[1], e),
     }
 }
@@ -0,0 +1,26 @@
+use std::ops::Range;
+
+/// A non-fatal irregularity found in a partial HTTP request
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomaly {
+    pub span: Range<usize>,
+    pub kind: AnomalyKind,
+}
+
+impl Anomaly {
+    pub fn new(span: Range<usize>, kind: AnomalyKind) -> Self {
+        Self { span, kind }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnomalyKind {
+    /// More than one whitespace character between request line parts
+    MultipleSpaces,
+    /// A request line with a uri but no http version
+    MissingVersion,
+    /// A header line without a `:`, or the configured separator, separating key and value
+    HeaderWithoutColon,
+    /// Spaces or tabs before the end of a request or header line
+    TrailingWhitespace,
+}
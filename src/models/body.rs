@@ -1,7 +1,34 @@
 pub type PossibleHttpBody = Option<String>;
 
+/// Whether a message has a body, distinguishing an empty body from none at all
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyState {
+    /// There is no body
+    Absent,
+    /// There is a body but it's empty, e.g. a `POST` with `Content-Length: 0`
+    Empty,
+    /// There is a non-empty body
+    Present,
+}
+
 pub trait HttpBody {
     fn get_body(&self) -> &PossibleHttpBody;
 
     fn set_body(&mut self, value: PossibleHttpBody);
+
+    /// Append a chunk to the existing body, creating it if undefined
+    fn append_body(&mut self, chunk: &str) {
+        let mut body = self.get_body().clone().unwrap_or_default();
+        body.push_str(chunk);
+        self.set_body(Some(body));
+    }
+
+    /// Get whether the body is absent, empty, or present
+    fn body_state(&self) -> BodyState {
+        match self.get_body().as_deref() {
+            None => BodyState::Absent,
+            Some("") => BodyState::Empty,
+            Some(_) => BodyState::Present,
+        }
+    }
 }
@@ -0,0 +1,86 @@
+/// A range from a `Range` header value in the `bytes` unit
+///
+/// ```skip
+/// Range: bytes=0-499, 500-, -500
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ByteRange {
+    /// The first byte position, or `None` for a suffix range like `-500`
+    pub start: Option<u64>,
+    /// The inclusive last byte position, or the suffix length when `start` is `None`
+    pub end: Option<u64>,
+}
+
+impl ByteRange {
+    /// Parse all ranges from a header value, returning an empty list if malformed
+    pub fn parse_all(value: &str) -> Vec<Self> {
+        let Some((unit, ranges)) = value.split_once('=') else {
+            return vec![];
+        };
+
+        if !unit.trim().eq_ignore_ascii_case("bytes") {
+            return vec![];
+        }
+
+        ranges
+            .split(',')
+            .map(|range| Self::parse(range.trim()))
+            .collect::<Option<Vec<Self>>>()
+            .unwrap_or_default()
+    }
+
+    /// Parse one `start-end`, `start-`, or `-suffix` range
+    fn parse(range: &str) -> Option<Self> {
+        let (start, end) = range.split_once('-')?;
+
+        let parse_position = |position: &str| match position.trim() {
+            "" => Ok(None),
+            position => position.parse().map(Some),
+        };
+
+        let start = parse_position(start).ok()?;
+        let end = parse_position(end).ok()?;
+
+        match (start, end) {
+            (None, None) => None,
+            (Some(start), Some(end)) if start > end => None,
+            _ => Some(Self { start, end }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod byte_range_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_all() {
+        assert_eq!(
+            ByteRange::parse_all("bytes=0-499, 500-,-500"),
+            vec![
+                ByteRange {
+                    start: Some(0),
+                    end: Some(499)
+                },
+                ByteRange {
+                    start: Some(500),
+                    end: None
+                },
+                ByteRange {
+                    start: None,
+                    end: Some(500)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_all_malformed() {
+        assert!(ByteRange::parse_all("bytes=").is_empty());
+        assert!(ByteRange::parse_all("bytes=-").is_empty());
+        assert!(ByteRange::parse_all("bytes=10-5").is_empty());
+        assert!(ByteRange::parse_all("bytes=0-1,x-2").is_empty());
+        assert!(ByteRange::parse_all("items=0-1").is_empty());
+        assert!(ByteRange::parse_all("0-1").is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+use std::io::Read;
+
+use crate::{
+    error::Error,
+    models::{HttpResponse, headers::filter_headers},
+};
+
+impl HttpResponse {
+    /// Get the stored body bytes decoded using the `Content-Encoding` header
+    ///
+    /// The stored body is a string so only text encodings like `identity` can be
+    /// decoded from it. Use [HttpResponse::decoded_body_from] for compressed bytes.
+    pub fn decoded_body(&self) -> Result<Vec<u8>, Error> {
+        self.decoded_body_from(self.body.as_deref().unwrap_or_default().as_bytes())
+    }
+
+    /// Decode raw body bytes using the `Content-Encoding` header
+    ///
+    /// `gzip`, `deflate`, and `br` are supported and multiple encodings are decoded
+    /// in reverse order. The body is returned unchanged without an encoding and
+    /// unknown encodings are a [Error::UnsupportedEncoding] error.
+    pub fn decoded_body_from(&self, body: &[u8]) -> Result<Vec<u8>, Error> {
+        let encodings: Vec<&str> = filter_headers(&self.headers, "Content-Encoding")
+            .flat_map(|header| header.value().split(','))
+            .map(str::trim)
+            .filter(|encoding| !encoding.is_empty())
+            .collect();
+
+        decode_body(body, &encodings)
+    }
+}
+
+/// Decode bytes by reversing each content encoding from last to first applied
+fn decode_body(body: &[u8], encodings: &[&str]) -> Result<Vec<u8>, Error> {
+    let mut decoded = body.to_vec();
+
+    for encoding in encodings.iter().rev() {
+        let mut output = vec![];
+
+        match encoding.to_ascii_lowercase().as_str() {
+            "identity" => continue,
+            "gzip" | "x-gzip" => {
+                flate2::read::GzDecoder::new(decoded.as_slice()).read_to_end(&mut output)?
+            }
+            "deflate" => {
+                flate2::read::ZlibDecoder::new(decoded.as_slice()).read_to_end(&mut output)?
+            }
+            "br" => brotli::Decompressor::new(decoded.as_slice(), 4096).read_to_end(&mut output)?,
+            _ => {
+                return Err(Error::UnsupportedEncoding {
+                    encoding: encoding.to_string(),
+                });
+            }
+        };
+
+        decoded = output;
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod compression_tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn deflate(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::ZlibEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn brotli(bytes: &[u8]) -> Vec<u8> {
+        let mut output = vec![];
+        brotli::CompressorWriter::new(&mut output, 4096, 5, 22)
+            .write_all(bytes)
+            .unwrap();
+        output
+    }
+
+    #[test]
+    fn test_decode_body() {
+        assert_eq!(decode_body(&gzip(b"body"), &["gzip"]), Ok(b"body".to_vec()));
+        assert_eq!(
+            decode_body(&deflate(b"body"), &["deflate"]),
+            Ok(b"body".to_vec())
+        );
+        assert_eq!(decode_body(&brotli(b"body"), &["br"]), Ok(b"body".to_vec()));
+        assert_eq!(
+            decode_body(&brotli(&gzip(b"body")), &["gzip", "identity", "BR"]),
+            Ok(b"body".to_vec())
+        );
+        assert!(matches!(
+            decode_body(b"body", &["gzip"]),
+            Err(Error::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_decoded_body() {
+        let response = HttpResponse::new(200.into(), vec![], Some("body"));
+        assert_eq!(response.decoded_body(), Ok(b"body".to_vec()));
+
+        let response = HttpResponse::new(
+            200.into(),
+            vec!["content-encoding: compress".into()],
+            Some("body"),
+        );
+        assert_eq!(
+            response.decoded_body(),
+            Err(Error::UnsupportedEncoding {
+                encoding: "compress".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_decoded_body_from() {
+        let response = HttpResponse::new(200.into(), vec!["Content-Encoding: gzip".into()], None);
+        assert_eq!(
+            response.decoded_body_from(&gzip(b"body")),
+            Ok(b"body".to_vec())
+        );
+
+        let response = HttpResponse::new(
+            200.into(),
+            vec![
+                "Content-Encoding: gzip".into(),
+                "Content-Encoding: br".into(),
+            ],
+            None,
+        );
+        assert_eq!(
+            response.decoded_body_from(&brotli(&gzip(b"body"))),
+            Ok(b"body".to_vec())
+        );
+
+        let response = HttpResponse::new(200.into(), vec![], None);
+        assert_eq!(
+            response.decoded_body_from(&gzip(b"body")),
+            Ok(gzip(b"body"))
+        );
+    }
+}
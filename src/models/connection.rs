@@ -0,0 +1,97 @@
+use crate::models::{HttpHeader, HttpVersion, headers::filter_headers};
+
+/// Headers that only apply to a single connection and aren't forwarded by proxies
+pub const HOP_BY_HOP_HEADERS: [&str; 10] = [
+    "Connection",
+    "Keep-Alive",
+    "Proxy-Authenticate",
+    "Proxy-Authorization",
+    "Proxy-Connection",
+    "TE",
+    "Trailer",
+    "Trailers",
+    "Transfer-Encoding",
+    "Upgrade",
+];
+
+/// Remove [HOP_BY_HOP_HEADERS] and any headers named in a `Connection` header value
+pub(crate) fn strip_hop_by_hop(headers: &mut Vec<HttpHeader>) {
+    let connection_tokens: Vec<String> = filter_headers(headers, "Connection")
+        .flat_map(|header| header.value().split(','))
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+        .collect();
+
+    headers.retain(|header| {
+        !HOP_BY_HOP_HEADERS.iter().any(|key| header.has_key(key))
+            && !connection_tokens.iter().any(|key| header.has_key(key))
+    });
+}
+
+/// Check if any `Connection` header lists the token
+pub(crate) fn connection_has_token(headers: &[HttpHeader], token: &str) -> bool {
+    filter_headers(headers, "Connection").any(|header| header.has_token(token))
+}
+
+/// Check if the connection should persist after the message
+///
+/// HTTP/1.1 and later default to keep-alive unless `Connection: close` is set.
+/// Earlier versions default to close unless `Connection: keep-alive` is set.
+pub(crate) fn should_keep_alive(http_version: &HttpVersion, headers: &[HttpHeader]) -> bool {
+    if http_version
+        .major_minor()
+        .is_some_and(|version| version >= (1, 1))
+    {
+        !connection_has_token(headers, "close")
+    } else {
+        connection_has_token(headers, "keep-alive")
+    }
+}
+
+#[cfg(test)]
+mod connection_tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_hop_by_hop() {
+        let mut headers: Vec<HttpHeader> = vec![
+            "Host: example.com".into(),
+            "connection: keep-alive, X-Trace".into(),
+            "Keep-Alive: timeout=5".into(),
+            "x-trace: abc".into(),
+            "Proxy-Connection: keep-alive".into(),
+            "te: trailers".into(),
+            "Transfer-Encoding: chunked".into(),
+            "Upgrade: websocket".into(),
+            "Accept: */*".into(),
+        ];
+
+        strip_hop_by_hop(&mut headers);
+
+        assert_eq!(
+            headers,
+            vec![
+                HttpHeader::new("Host", "example.com"),
+                HttpHeader::new("Accept", "*/*")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_http_1_1_defaults_to_keep_alive() {
+        assert!(should_keep_alive(&"HTTP/1.1".into(), &[]));
+        assert!(!should_keep_alive(
+            &"HTTP/1.1".into(),
+            &["Connection: Close".into()]
+        ));
+    }
+
+    #[test]
+    fn test_http_1_0_defaults_to_close() {
+        assert!(!should_keep_alive(&"HTTP/1.0".into(), &[]));
+        assert!(should_keep_alive(
+            &"HTTP/1.0".into(),
+            &["connection: Keep-Alive".into()]
+        ));
+    }
+}
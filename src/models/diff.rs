@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+
+use crate::models::HttpHeader;
+
+/// A single structural difference between two HTTP requests
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestDiff {
+    MethodChanged,
+    UriChanged,
+    VersionChanged,
+    HeaderAdded(String),
+    HeaderRemoved(String),
+    HeaderChanged(String),
+    BodyChanged,
+}
+
+/// Diff two header lists by lowercased key so reordering isn't a change
+pub(crate) fn diff_headers(left: &[HttpHeader], right: &[HttpHeader]) -> Vec<RequestDiff> {
+    let left = group_headers(left);
+    let right = group_headers(right);
+
+    let mut diffs = vec![];
+
+    for (lowercase_key, (key, left_values)) in left.iter() {
+        match right.get(lowercase_key) {
+            Some((_, right_values)) if left_values != right_values => {
+                diffs.push(RequestDiff::HeaderChanged(key.to_string()));
+            }
+            Some(_) => {}
+            None => diffs.push(RequestDiff::HeaderRemoved(key.to_string())),
+        }
+    }
+
+    for (lowercase_key, (key, _)) in right.iter() {
+        if !left.contains_key(lowercase_key) {
+            diffs.push(RequestDiff::HeaderAdded(key.to_string()));
+        }
+    }
+
+    diffs
+}
+
+/// Group header values by lowercased key, keeping the first seen key spelling
+fn group_headers(headers: &[HttpHeader]) -> BTreeMap<String, (&str, Vec<&str>)> {
+    let mut grouped: BTreeMap<String, (&str, Vec<&str>)> = BTreeMap::new();
+
+    for header in headers {
+        grouped
+            .entry(header.key().to_ascii_lowercase())
+            .or_insert_with(|| (header.key(), vec![]))
+            .1
+            .push(header.value());
+    }
+
+    grouped
+}
+
+#[cfg(test)]
+mod diff_tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_headers_ignores_order_and_case() {
+        let left: Vec<HttpHeader> = vec!["Accept: */*".into(), "X-Key: 123".into()];
+        let right: Vec<HttpHeader> = vec!["x-key: 123".into(), "accept: */*".into()];
+
+        assert_eq!(diff_headers(&left, &right), vec![]);
+    }
+
+    #[test]
+    fn test_diff_headers_added_removed_changed() {
+        let left: Vec<HttpHeader> = vec!["Accept: */*".into(), "X-Key: 123".into()];
+        let right: Vec<HttpHeader> = vec!["X-Key: 456".into(), "X-New: abc".into()];
+
+        assert_eq!(
+            diff_headers(&left, &right),
+            vec![
+                RequestDiff::HeaderRemoved("Accept".to_string()),
+                RequestDiff::HeaderChanged("X-Key".to_string()),
+                RequestDiff::HeaderAdded("X-New".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_headers_duplicate_values() {
+        let left: Vec<HttpHeader> = vec!["Accept: a".into(), "Accept: b".into()];
+        let right: Vec<HttpHeader> = vec!["Accept: a".into()];
+
+        assert_eq!(
+            diff_headers(&left, &right),
+            vec![RequestDiff::HeaderChanged("Accept".to_string())]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use encoding_rs::Encoding;
+
+use crate::{error::Error, models::HttpResponse};
+
+impl HttpResponse {
+    /// Get the body text, checking the `Content-Type` charset is known
+    ///
+    /// The stored body is already decoded text so it's returned unchanged. Use
+    /// [HttpResponse::body_text_from] to decode raw body bytes. An unknown charset is
+    /// a [Error::InvalidEncoding] error. A missing body is an empty string.
+    pub fn body_text(&self) -> Result<String, Error> {
+        let charset = self.charset();
+
+        Encoding::for_label(charset.trim().as_bytes())
+            .map(|_| self.body.clone().unwrap_or_default())
+            .ok_or(Error::InvalidEncoding { charset })
+    }
+
+    /// Decode raw body bytes using the `Content-Type` charset, defaulting to UTF-8
+    ///
+    /// An unknown charset or bytes that aren't valid in it are a
+    /// [Error::InvalidEncoding] error.
+    pub fn body_text_from(&self, body: &[u8]) -> Result<String, Error> {
+        decode_text(body, &self.charset())
+    }
+
+    /// Get the `Content-Type` charset, defaulting to UTF-8
+    fn charset(&self) -> String {
+        self.content_type()
+            .and_then(|media_type| media_type.charset)
+            .unwrap_or_else(|| "utf-8".to_string())
+    }
+}
+
+/// Decode bytes with the encoding for a charset label like `ISO-8859-1`
+fn decode_text(bytes: &[u8], charset: &str) -> Result<String, Error> {
+    let invalid_encoding = || Error::InvalidEncoding {
+        charset: charset.to_string(),
+    };
+
+    Encoding::for_label(charset.trim().as_bytes())
+        .ok_or_else(invalid_encoding)?
+        .decode_without_bom_handling_and_without_replacement(bytes)
+        .map(|text| text.into_owned())
+        .ok_or_else(invalid_encoding)
+}
+
+#[cfg(test)]
+mod encoding_tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_text() {
+        assert_eq!(
+            decode_text(b"caf\xe9", "ISO-8859-1"),
+            Ok("café".to_string())
+        );
+        assert_eq!(decode_text(b"\x82\xa0", "Shift_JIS"), Ok("あ".to_string()));
+        assert_eq!(
+            decode_text(b"caf\xe9", "utf-8"),
+            Err(Error::InvalidEncoding {
+                charset: "utf-8".to_string()
+            })
+        );
+        assert_eq!(
+            decode_text(b"body", "unknown"),
+            Err(Error::InvalidEncoding {
+                charset: "unknown".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_body_text() {
+        let response = HttpResponse::new(200.into(), vec![], Some("café"));
+        assert_eq!(response.body_text(), Ok("café".to_string()));
+
+        let response = HttpResponse::new(
+            200.into(),
+            vec!["Content-Type: text/plain; charset=ISO-8859-1".into()],
+            Some("café"),
+        );
+        assert_eq!(response.body_text(), Ok("café".to_string()));
+
+        let response = HttpResponse::new(
+            200.into(),
+            vec!["Content-Type: text/plain; charset=x-unknown".into()],
+            Some("body"),
+        );
+        assert_eq!(
+            response.body_text(),
+            Err(Error::InvalidEncoding {
+                charset: "x-unknown".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_body_text_from() {
+        let response = HttpResponse::new(
+            200.into(),
+            vec!["Content-Type: text/plain; charset=ISO-8859-1".into()],
+            None,
+        );
+        assert_eq!(response.body_text_from(b"caf\xe9"), Ok("café".to_string()));
+
+        let response = HttpResponse::new(200.into(), vec![], None);
+        assert_eq!(
+            response.body_text_from("café".as_bytes()),
+            Ok("café".to_string())
+        );
+        assert_eq!(
+            response.body_text_from(b"caf\xe9"),
+            Err(Error::InvalidEncoding {
+                charset: "utf-8".to_string()
+            })
+        );
+    }
+}
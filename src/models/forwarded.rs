@@ -0,0 +1,143 @@
+/// An element of an RFC 7239 `Forwarded` header for one proxy hop
+///
+/// ```skip
+/// Forwarded: for=192.0.2.60;proto=http;by=203.0.113.43
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ForwardedElement {
+    /// The `for` parameter identifying the client making the request to the proxy
+    pub for_: Option<String>,
+    /// The `by` parameter identifying the proxy interface receiving the request
+    pub by: Option<String>,
+    /// The `host` parameter with the original `Host` header value
+    pub host: Option<String>,
+    /// The `proto` parameter with the original protocol, e.g. `https`
+    pub proto: Option<String>,
+}
+
+impl ForwardedElement {
+    /// Parse all comma separated elements from a header value
+    ///
+    /// Parameter names are matched ignoring ASCII case, quoted values are unquoted,
+    /// and unknown parameters are skipped.
+    pub fn parse_all(value: &str) -> Vec<Self> {
+        split_unquoted(value, ',')
+            .into_iter()
+            .filter(|element| !element.trim().is_empty())
+            .map(Self::parse)
+            .collect()
+    }
+
+    fn parse(element: &str) -> Self {
+        let mut parsed = Self::default();
+
+        for pair in split_unquoted(element, ';') {
+            let Some((name, value)) = pair.split_once('=') else {
+                continue;
+            };
+
+            let value = unquote(value.trim());
+
+            match name.trim().to_ascii_lowercase().as_str() {
+                "for" => parsed.for_ = Some(value),
+                "by" => parsed.by = Some(value),
+                "host" => parsed.host = Some(value),
+                "proto" => parsed.proto = Some(value),
+                _ => {}
+            }
+        }
+
+        parsed
+    }
+}
+
+/// Split on a separator outside of double quoted strings
+fn split_unquoted(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (idx, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            c if c == separator && !in_quotes => {
+                parts.push(&value[start..idx]);
+                start = idx + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(&value[start..]);
+    parts
+}
+
+/// Remove surrounding double quotes and backslash escapes from a value
+fn unquote(value: &str) -> String {
+    match value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        Some(quoted) => {
+            let mut unquoted = String::new();
+            let mut chars = quoted.chars();
+
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => unquoted.extend(chars.next()),
+                    c => unquoted.push(c),
+                }
+            }
+
+            unquoted
+        }
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod forwarded_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_all() {
+        let elements = ForwardedElement::parse_all(
+            r#"For="[2001:db8:cafe::17]:4711";proto=https, for=192.0.2.60;by=203.0.113.43;host="a,b""#,
+        );
+
+        assert_eq!(
+            elements,
+            vec![
+                ForwardedElement {
+                    for_: Some("[2001:db8:cafe::17]:4711".to_string()),
+                    proto: Some("https".to_string()),
+                    ..Default::default()
+                },
+                ForwardedElement {
+                    for_: Some("192.0.2.60".to_string()),
+                    by: Some("203.0.113.43".to_string()),
+                    host: Some("a,b".to_string()),
+                    proto: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_all_empty() {
+        assert!(ForwardedElement::parse_all("").is_empty());
+        assert_eq!(
+            ForwardedElement::parse_all("secret=1"),
+            vec![ForwardedElement::default()]
+        );
+    }
+
+    #[test]
+    fn test_unquote() {
+        assert_eq!(unquote(r#""a\"b""#), r#"a"b"#);
+        assert_eq!(unquote("plain"), "plain");
+    }
+}
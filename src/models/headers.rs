@@ -1,5 +1,7 @@
 use core::fmt;
 
+use crate::error::Error;
+
 /// An HTTP header key & value
 ///
 /// ```skip
@@ -14,6 +16,22 @@ impl HttpHeader {
         Self(key.to_string(), value.to_string())
     }
 
+    /// Parse a `key: value` header line, trimming the value and any line ending
+    pub fn parse(line: &str) -> Result<Self, Error> {
+        Self::from_with_sep(line, ':')
+    }
+
+    /// Parse a header line with a custom separator between the key and value, e.g. `=`
+    ///
+    /// Returns [Error::InvalidHeader] if the separator isn't found.
+    pub fn from_with_sep(line: &str, sep: char) -> Result<Self, Error> {
+        let (key, value) = line
+            .split_once(sep)
+            .ok_or_else(|| Error::invalid_header(line.trim_end()))?;
+
+        Ok(HttpHeader::new(key, value.trim()))
+    }
+
     pub fn key(&self) -> &str {
         &self.0
     }
@@ -21,6 +39,142 @@ impl HttpHeader {
     pub fn value(&self) -> &str {
         &self.1
     }
+
+    pub fn set_key(&mut self, key: &str) {
+        self.0 = key.to_string();
+    }
+
+    /// Check if the header key matches, ignoring ASCII case
+    pub fn has_key(&self, key: &str) -> bool {
+        self.0.eq_ignore_ascii_case(key)
+    }
+
+    /// Get the key with the first letter of each `-` separated word uppercased
+    ///
+    /// `content-TYPE` becomes `Content-Type`.
+    pub fn canonical_key(&self) -> String {
+        self.0
+            .split('-')
+            .map(|word| {
+                let mut chars = word.chars();
+
+                match chars.next() {
+                    Some(first) => {
+                        first.to_ascii_uppercase().to_string()
+                            + &chars.as_str().to_ascii_lowercase()
+                    }
+                    None => String::new(),
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("-")
+    }
+
+    /// Check if the comma separated value contains token, ignoring ASCII case
+    pub fn has_token(&self, token: &str) -> bool {
+        self.1
+            .split(',')
+            .any(|value| value.trim().eq_ignore_ascii_case(token))
+    }
+}
+
+/// Check if two header lists have the same headers ignoring order and key case
+///
+/// Headers are compared as multisets so duplicates must appear the same number of
+/// times. Values are compared exactly.
+pub fn headers_equivalent(a: &[HttpHeader], b: &[HttpHeader]) -> bool {
+    fn normalized(headers: &[HttpHeader]) -> Vec<(String, &str)> {
+        let mut headers: Vec<(String, &str)> = headers
+            .iter()
+            .map(|header| (header.key().to_ascii_lowercase(), header.value()))
+            .collect();
+        headers.sort();
+        headers
+    }
+
+    a.len() == b.len() && normalized(a) == normalized(b)
+}
+
+/// Parse a block of `key: value` lines in to headers
+///
+/// Lines starting with a space or tab are folded in to the previous header's value
+/// with a single space. Blank lines, lines without a `:`, and continuation lines
+/// without a previous header are skipped.
+pub(crate) fn parse_headers(input: &str) -> Vec<HttpHeader> {
+    let mut headers: Vec<HttpHeader> = vec![];
+
+    for line in input.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if line.starts_with([' ', '\t']) {
+            if let Some(header) = headers.last_mut() {
+                if !header.1.is_empty() {
+                    header.1.push(' ');
+                }
+                header.1.push_str(line.trim());
+            }
+            continue;
+        }
+
+        if let Ok(header) = HttpHeader::parse(line) {
+            headers.push(header);
+        }
+    }
+
+    headers
+}
+
+/// Parse a header line, joining obsolete folded continuation lines with a single space
+pub(crate) fn parse_folded_header(line: &str) -> Result<HttpHeader, Error> {
+    let mut lines = line.lines();
+    let mut header = HttpHeader::parse(lines.next().unwrap_or_default())?;
+
+    for continuation in lines.map(str::trim).filter(|line| !line.is_empty()) {
+        if !header.1.is_empty() {
+            header.1.push(' ');
+        }
+        header.1.push_str(continuation);
+    }
+
+    Ok(header)
+}
+
+/// Find the first header matching key, ignoring ASCII case
+pub(crate) fn find_header<'a>(headers: &'a [HttpHeader], key: &str) -> Option<&'a HttpHeader> {
+    headers.iter().find(|header| header.has_key(key))
+}
+
+/// Find all headers matching key, ignoring ASCII case
+pub(crate) fn filter_headers<'a>(
+    headers: &'a [HttpHeader],
+    key: &'a str,
+) -> impl Iterator<Item = &'a HttpHeader> {
+    headers.iter().filter(move |header| header.has_key(key))
+}
+
+/// Collapse all headers matching key in to the first, joining values with `, `
+///
+/// Keys are matched ignoring ASCII case and the first key spelling is kept.
+pub(crate) fn fold_headers(headers: &mut Vec<HttpHeader>, key: &str) {
+    let Some(first_idx) = headers.iter().position(|header| header.has_key(key)) else {
+        return;
+    };
+
+    let value = filter_headers(headers, key)
+        .map(|header| header.value())
+        .collect::<Vec<&str>>()
+        .join(", ");
+
+    headers[first_idx].1 = value;
+
+    let mut idx = 0;
+    headers.retain(|header| {
+        let keep = idx <= first_idx || !header.has_key(key);
+        idx += 1;
+        keep
+    });
 }
 
 impl fmt::Display for HttpHeader {
@@ -35,18 +189,24 @@ impl From<(&str, &str)> for HttpHeader {
     }
 }
 
-impl From<&str> for HttpHeader {
-    fn from(value: &str) -> Self {
-        let index = value
-            .chars()
-            .position(|c| c == ':')
-            .expect("should find ':' in header string");
-
-        let (key, value) = value.split_at(index);
+impl From<HttpHeader> for (String, String) {
+    fn from(value: HttpHeader) -> Self {
+        (value.0, value.1)
+    }
+}
 
-        let value = value[1..].trim();
+impl From<&HttpHeader> for (String, String) {
+    fn from(value: &HttpHeader) -> Self {
+        (value.0.clone(), value.1.clone())
+    }
+}
 
-        (key, value).into()
+/// Parses a `key: value` line, or a line without a `:` as a key with an empty value
+///
+/// Use [HttpHeader::parse] to reject lines without a `:`.
+impl From<&str> for HttpHeader {
+    fn from(value: &str) -> Self {
+        HttpHeader::parse(value).unwrap_or_else(|_| HttpHeader::new(value.trim_end(), ""))
     }
 }
 
@@ -61,6 +221,145 @@ mod tests {
         assert_eq!(header.1, "application/json");
     }
 
+    #[test]
+    fn test_http_header_from_with_sep() {
+        assert_eq!(
+            HttpHeader::from_with_sep("x-key=123\r\n", '='),
+            Ok(HttpHeader::new("x-key", "123"))
+        );
+        assert_eq!(
+            HttpHeader::from_with_sep("x-key\t123", '\t'),
+            Ok(HttpHeader::new("x-key", "123"))
+        );
+        assert_eq!(
+            HttpHeader::from_with_sep("x-key: 123", '='),
+            Err(Error::invalid_header("x-key: 123"))
+        );
+    }
+
+    #[test]
+    fn test_http_header_set_key() {
+        let mut header = HttpHeader::new("content-type", "application/json");
+        header.set_key("Content-Type");
+        assert_eq!(header.key(), "Content-Type");
+        assert_eq!(header.value(), "application/json");
+    }
+
+    #[test]
+    fn test_http_header_has_key() {
+        let header = HttpHeader::new("Content-Type", "application/json");
+        assert!(header.has_key("content-type"));
+        assert!(header.has_key("CONTENT-TYPE"));
+        assert!(!header.has_key("Content-Length"));
+    }
+
+    #[test]
+    fn test_http_header_has_token() {
+        let header = HttpHeader::new("Connection", "keep-alive, Upgrade");
+        assert!(header.has_token("upgrade"));
+        assert!(header.has_token("Keep-Alive"));
+        assert!(!header.has_token("close"));
+    }
+
+    #[test]
+    fn test_http_header_canonical_key() {
+        assert_eq!(
+            HttpHeader::new("content-TYPE", "").canonical_key(),
+            "Content-Type"
+        );
+        assert_eq!(
+            HttpHeader::new("X-API-KEY", "").canonical_key(),
+            "X-Api-Key"
+        );
+        assert_eq!(HttpHeader::new("etag", "").canonical_key(), "Etag");
+    }
+
+    #[test]
+    fn test_http_header_into_tuple() {
+        let header = HttpHeader::new("Content-Type", "text/plain");
+
+        let borrowed: (String, String) = (&header).into();
+        let owned: (String, String) = header.into();
+
+        assert_eq!(borrowed, owned);
+        assert_eq!(
+            owned,
+            ("Content-Type".to_string(), "text/plain".to_string())
+        );
+
+        let headers: Vec<HttpHeader> = vec!["a: 1".into(), "b: 2".into()];
+        let map: std::collections::HashMap<String, String> =
+            headers.iter().map(Into::into).collect();
+
+        assert_eq!(map["b"], "2");
+    }
+
+    #[test]
+    fn test_parse_headers() {
+        let headers =
+            parse_headers("Accept: */*\r\n\r\nX-Folded: a\r\n  b\r\n\tc\r\ninvalid\r\nX-Empty:\n");
+
+        assert_eq!(
+            headers,
+            vec![
+                HttpHeader::new("Accept", "*/*"),
+                HttpHeader::new("X-Folded", "a b c"),
+                HttpHeader::new("X-Empty", ""),
+            ]
+        );
+
+        assert!(parse_headers("  orphan\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_folded_header() {
+        assert_eq!(
+            parse_folded_header("X-Folded: a\r\n  b\r\n\tc\r\n"),
+            Ok(HttpHeader::new("X-Folded", "a b c"))
+        );
+        assert_eq!(
+            parse_folded_header("X-Empty:\n b\n"),
+            Ok(HttpHeader::new("X-Empty", "b"))
+        );
+        assert_eq!(
+            parse_folded_header("invalid\n b\n"),
+            Err(Error::invalid_header("invalid"))
+        );
+    }
+
+    #[test]
+    fn test_headers_equivalent() {
+        let a: Vec<HttpHeader> = vec!["Accept: */*".into(), "X-Key: 1".into(), "X-Key: 2".into()];
+        let b: Vec<HttpHeader> = vec!["x-key: 2".into(), "ACCEPT: */*".into(), "x-key: 1".into()];
+        assert!(headers_equivalent(&a, &b));
+
+        let c: Vec<HttpHeader> = vec!["Accept: */*".into(), "X-Key: 1".into(), "X-Key: 1".into()];
+        assert!(!headers_equivalent(&a, &c));
+
+        let d: Vec<HttpHeader> = vec!["Accept: */*".into(), "X-Key: 1".into()];
+        assert!(!headers_equivalent(&a, &d));
+    }
+
+    #[test]
+    fn test_fold_headers() {
+        let mut headers: Vec<HttpHeader> = vec![
+            "Accept: text/html".into(),
+            "X-Key: 1".into(),
+            "accept: application/json".into(),
+            "ACCEPT: */*".into(),
+        ];
+
+        fold_headers(&mut headers, "Accept");
+
+        assert_eq!(
+            headers,
+            vec![
+                HttpHeader::new("Accept", "text/html, application/json, */*"),
+                HttpHeader::new("X-Key", "1")
+            ]
+        );
+    }
+
     #[test]
     fn test_http_header_display() {
         let header = HttpHeader::new("Content-Type", "application/json");
@@ -79,5 +378,30 @@ mod tests {
         let header: HttpHeader = "Content-Type: application/json".into();
         assert_eq!(header.key(), "Content-Type");
         assert_eq!(header.value(), "application/json");
+        let header: HttpHeader = "x-flag\r\n".into();
+        assert_eq!(header, HttpHeader::new("x-flag", ""));
+    }
+
+    #[test]
+    fn test_http_header_parse() {
+        assert_eq!(
+            HttpHeader::parse("x-key: a:b\r\n"),
+            Ok(HttpHeader::new("x-key", "a:b"))
+        );
+        assert_eq!(
+            HttpHeader::parse("x-key\n"),
+            Err(Error::invalid_header("x-key"))
+        );
+    }
+
+    #[test]
+    fn test_http_header_from_str_with_empty_value() {
+        let header: HttpHeader = "X-Trace:".into();
+        assert_eq!(header.key(), "X-Trace");
+        assert_eq!(header.value(), "");
+
+        let header: HttpHeader = "X-Trace: \t ".into();
+        assert_eq!(header.key(), "X-Trace");
+        assert_eq!(header.value(), "");
     }
 }
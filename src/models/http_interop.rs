@@ -0,0 +1,174 @@
+use crate::{
+    error::Error,
+    models::{HttpHeader, HttpResponse, HttpStatusCode, HttpVersion},
+};
+
+/// Convert to the `http` crate version, failing for versions it doesn't support
+fn to_http_version(http_version: &HttpVersion) -> Result<http::Version, Error> {
+    match http_version.major_minor() {
+        Some((0, 9)) => Ok(http::Version::HTTP_09),
+        Some((1, 0)) => Ok(http::Version::HTTP_10),
+        Some((1, 1)) => Ok(http::Version::HTTP_11),
+        Some((2, 0)) => Ok(http::Version::HTTP_2),
+        Some((3, 0)) => Ok(http::Version::HTTP_3),
+        _ => Err(Error::UnsupportedHttpVersion {
+            http_version: http_version.to_string(),
+        }),
+    }
+}
+
+/// Convert from the `http` crate version
+fn from_http_version(version: http::Version) -> Result<HttpVersion, Error> {
+    match version {
+        http::Version::HTTP_09 => Ok("HTTP/0.9".into()),
+        http::Version::HTTP_10 => Ok("HTTP/1.0".into()),
+        http::Version::HTTP_11 => Ok("HTTP/1.1".into()),
+        http::Version::HTTP_2 => Ok("HTTP/2".into()),
+        http::Version::HTTP_3 => Ok("HTTP/3".into()),
+        version => Err(Error::UnsupportedHttpVersion {
+            http_version: format!("{version:?}"),
+        }),
+    }
+}
+
+impl TryFrom<HttpResponse> for http::Response<Vec<u8>> {
+    type Error = Error;
+
+    fn try_from(value: HttpResponse) -> Result<Self, Self::Error> {
+        let status_code = value.status_code.as_u16();
+
+        let mut response = http::Response::new(value.body.unwrap_or_default().into_bytes());
+
+        *response.status_mut() = http::StatusCode::from_u16(status_code)
+            .map_err(|_| Error::invalid_status_code(&status_code.to_string()))?;
+
+        *response.version_mut() = to_http_version(&value.http_version)?;
+
+        for header in value.headers {
+            let key = http::HeaderName::from_bytes(header.key().as_bytes())
+                .map_err(|_| Error::invalid_header(header.key()))?;
+
+            let value = http::HeaderValue::from_str(header.value())
+                .map_err(|_| Error::invalid_header(header.key()))?;
+
+            response.headers_mut().append(key, value);
+        }
+
+        Ok(response)
+    }
+}
+
+impl TryFrom<http::Response<Vec<u8>>> for HttpResponse {
+    type Error = Error;
+
+    fn try_from(value: http::Response<Vec<u8>>) -> Result<Self, Self::Error> {
+        let (parts, body) = value.into_parts();
+
+        let headers = parts
+            .headers
+            .iter()
+            .map(|(key, value)| {
+                value
+                    .to_str()
+                    .map(|value| HttpHeader::new(key.as_str(), value))
+                    .map_err(|_| Error::invalid_header(key.as_str()))
+            })
+            .collect::<Result<Vec<HttpHeader>, Error>>()?;
+
+        let body = String::from_utf8(body).map_err(|_| Error::InvalidBody)?;
+
+        let mut response = HttpResponse::new(
+            HttpStatusCode::new(parts.status.as_u16()),
+            headers,
+            (!body.is_empty()).then_some(body.as_str()),
+        );
+
+        response.http_version = from_http_version(parts.version)?;
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod http_interop_tests {
+    use super::*;
+
+    #[test]
+    fn test_response_to_http_response() {
+        let mut response = HttpResponse::new(
+            201.into(),
+            vec![
+                "Content-Type: text/plain".into(),
+                "X-Key: 1".into(),
+                "x-key: 2".into(),
+            ],
+            Some("created"),
+        );
+        response.http_version = "HTTP/1.0".into();
+
+        let http_response: http::Response<Vec<u8>> = response.try_into().unwrap();
+
+        assert_eq!(http_response.status(), http::StatusCode::CREATED);
+        assert_eq!(http_response.version(), http::Version::HTTP_10);
+        assert_eq!(http_response.headers()["content-type"], "text/plain");
+        assert_eq!(
+            http_response
+                .headers()
+                .get_all("x-key")
+                .iter()
+                .collect::<Vec<_>>(),
+            vec!["1", "2"]
+        );
+        assert_eq!(http_response.body(), b"created");
+    }
+
+    #[test]
+    fn test_response_to_http_response_errors() {
+        let response = HttpResponse::new(1000.into(), vec![], None);
+        let result: Result<http::Response<Vec<u8>>, Error> = response.try_into();
+        assert_eq!(result.unwrap_err(), Error::invalid_status_code("1000"));
+
+        let response = HttpResponse::new(200.into(), vec![("Bad Key", "value").into()], None);
+        let result: Result<http::Response<Vec<u8>>, Error> = response.try_into();
+        assert_eq!(result.unwrap_err(), Error::invalid_header("Bad Key"));
+
+        let mut response = HttpResponse::new(200.into(), vec![], None);
+        response.http_version = "HTTP/4.2".into();
+        let result: Result<http::Response<Vec<u8>>, Error> = response.try_into();
+        assert_eq!(
+            result.unwrap_err(),
+            Error::UnsupportedHttpVersion {
+                http_version: "HTTP/4.2".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_http_response_to_response() {
+        let http_response = http::Response::builder()
+            .status(404)
+            .version(http::Version::HTTP_2)
+            .header("Content-Type", "text/plain")
+            .body(b"missing".to_vec())
+            .unwrap();
+
+        let response: HttpResponse = http_response.try_into().unwrap();
+
+        assert_eq!(response.status_code, HttpStatusCode::new(404));
+        assert_eq!(response.http_version.to_string(), "HTTP/2");
+        assert_eq!(
+            response.headers(),
+            &vec![HttpHeader::new("content-type", "text/plain")]
+        );
+        assert_eq!(response.body, Some("missing".to_string()));
+    }
+
+    #[test]
+    fn test_http_response_to_response_invalid_body() {
+        let http_response = http::Response::new(vec![0xff, 0xfe]);
+
+        let result: Result<HttpResponse, Error> = http_response.try_into();
+
+        assert_eq!(result.unwrap_err(), Error::InvalidBody);
+    }
+}
@@ -0,0 +1,79 @@
+use serde::de::DeserializeOwned;
+
+use crate::{error::Error, models::HttpRequest};
+
+impl HttpRequest {
+    /// Deserialize the JSON body as `T`
+    ///
+    /// A missing body is a [Error::BodyDeserialization] error.
+    pub fn body_json<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        let body = self
+            .body
+            .as_ref()
+            .ok_or_else(|| Error::BodyDeserialization {
+                message: "body is missing".to_string(),
+            })?;
+
+        serde_json::from_str(body).map_err(|error| Error::BodyDeserialization {
+            message: error.to_string(),
+        })
+    }
+
+    /// Deserialize the JSON body as an untyped [serde_json::Value]
+    pub fn body_json_value(&self) -> Result<serde_json::Value, Error> {
+        self.body_json()
+    }
+}
+
+#[cfg(test)]
+mod json_tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct User {
+        id: u64,
+        name: String,
+    }
+
+    #[test]
+    fn test_body_json() {
+        let request = HttpRequest::post(
+            "https://example.com",
+            vec!["Content-Type: application/json".into()],
+            Some(r#"{"id": 1, "name": "alice"}"#.to_string()),
+        );
+
+        assert_eq!(
+            request.body_json::<User>(),
+            Ok(User {
+                id: 1,
+                name: "alice".to_string()
+            })
+        );
+        assert_eq!(
+            request.body_json_value(),
+            Ok(serde_json::json!({"id": 1, "name": "alice"}))
+        );
+    }
+
+    #[test]
+    fn test_body_json_errors() {
+        let request = HttpRequest::post("https://example.com", vec![], None);
+
+        assert_eq!(
+            request.body_json_value(),
+            Err(Error::BodyDeserialization {
+                message: "body is missing".to_string()
+            })
+        );
+
+        let request = HttpRequest::post("https://example.com", vec![], Some("{".to_string()));
+
+        assert!(matches!(
+            request.body_json::<User>(),
+            Err(Error::BodyDeserialization { .. })
+        ));
+    }
+}
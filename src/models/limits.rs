@@ -0,0 +1,22 @@
+/// Limits applied while parsing untrusted HTTP messages
+///
+/// The default limits don't restrict parsing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseLimits {
+    /// The maximum number of header lines
+    pub max_headers: usize,
+    /// The maximum length of a request line or header line without its line ending
+    pub max_line_len: usize,
+    /// The maximum length of the whole message
+    pub max_total: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_headers: usize::MAX,
+            max_line_len: usize::MAX,
+            max_total: usize::MAX,
+        }
+    }
+}
@@ -0,0 +1,173 @@
+/// A parsed media type from a `Content-Type` header value
+///
+/// ```skip
+/// Content-Type: application/json; charset=utf-8
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaType {
+    /// The lowercased `type/subtype` without parameters
+    pub essence: String,
+    /// The value of the `charset` parameter, if defined
+    pub charset: Option<String>,
+    /// All parameters in order with lowercased names and unquoted values
+    pub params: Vec<(String, String)>,
+}
+
+impl MediaType {
+    /// Parse a media type from a header value, returning `None` if there is no essence
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.split(';');
+
+        let essence = parts.next()?.trim().to_ascii_lowercase();
+
+        if essence.is_empty() {
+            return None;
+        }
+
+        let params: Vec<(String, String)> = parts
+            .filter_map(|param| {
+                let (name, value) = param.split_once('=')?;
+                let name = name.trim().to_ascii_lowercase();
+                let value = value.trim().trim_matches('"').to_string();
+
+                (!name.is_empty()).then_some((name, value))
+            })
+            .collect();
+
+        let charset = params
+            .iter()
+            .find(|(name, _)| name == "charset")
+            .map(|(_, value)| value.clone());
+
+        Some(Self {
+            essence,
+            charset,
+            params,
+        })
+    }
+}
+
+/// A media range from an `Accept` header value with its quality
+///
+/// ```skip
+/// Accept: text/html, application/*;q=0.8, */*;q=0.1
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcceptRange {
+    /// The lowercased `type/subtype`, which can be a wildcard like `text/*` or `*/*`
+    pub media_type: String,
+    /// The `q` parameter weight from `0.0` to `1.0`, defaulting to `1.0`
+    pub quality: f32,
+    /// All parameters except `q` in order with lowercased names and unquoted values
+    pub params: Vec<(String, String)>,
+}
+
+impl AcceptRange {
+    /// Parse all media ranges from a header value sorted by descending quality
+    ///
+    /// Ranges with equal quality keep their order. Ranges without a media type or
+    /// with an invalid quality are skipped.
+    pub fn parse_all(value: &str) -> Vec<Self> {
+        let mut ranges: Vec<Self> = value.split(',').filter_map(Self::parse).collect();
+
+        ranges.sort_by(|a, b| b.quality.total_cmp(&a.quality));
+
+        ranges
+    }
+
+    fn parse(range: &str) -> Option<Self> {
+        let MediaType {
+            essence,
+            mut params,
+            ..
+        } = MediaType::parse(range)?;
+
+        let quality = match params.iter().position(|(name, _)| name == "q") {
+            Some(idx) => params.remove(idx).1.parse::<f32>().ok()?,
+            None => 1.0,
+        };
+
+        (0.0..=1.0).contains(&quality).then_some(Self {
+            media_type: essence,
+            quality,
+            params,
+        })
+    }
+}
+
+#[cfg(test)]
+mod media_type_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_essence_only() {
+        let media_type = MediaType::parse("application/json").unwrap();
+        assert_eq!(media_type.essence, "application/json");
+        assert_eq!(media_type.charset, None);
+        assert!(media_type.params.is_empty());
+    }
+
+    #[test]
+    fn test_parse_with_charset() {
+        let media_type = MediaType::parse("application/json; charset=utf-8").unwrap();
+        assert_eq!(media_type.essence, "application/json");
+        assert_eq!(media_type.charset, Some("utf-8".to_string()));
+        assert_eq!(
+            media_type.params,
+            vec![("charset".to_string(), "utf-8".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_parse_normalizes_case_and_quotes() {
+        let media_type =
+            MediaType::parse("Multipart/Form-Data; Boundary=\"abc\"; CHARSET=UTF-8").unwrap();
+        assert_eq!(media_type.essence, "multipart/form-data");
+        assert_eq!(media_type.charset, Some("UTF-8".to_string()));
+        assert_eq!(
+            media_type.params,
+            vec![
+                ("boundary".to_string(), "abc".to_string()),
+                ("charset".to_string(), "UTF-8".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_accept_range_parse_all() {
+        let ranges = AcceptRange::parse_all(
+            "text/*;q=0.5, application/json;version=2, */*;q=0.1, text/html",
+        );
+
+        let media_types: Vec<(&str, f32)> = ranges
+            .iter()
+            .map(|range| (range.media_type.as_str(), range.quality))
+            .collect();
+
+        assert_eq!(
+            media_types,
+            vec![
+                ("application/json", 1.0),
+                ("text/html", 1.0),
+                ("text/*", 0.5),
+                ("*/*", 0.1)
+            ]
+        );
+        assert_eq!(
+            ranges[0].params,
+            vec![("version".to_string(), "2".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_accept_range_parse_all_invalid() {
+        assert!(AcceptRange::parse_all("").is_empty());
+        assert!(AcceptRange::parse_all("text/html;q=x, text/plain;q=2").is_empty());
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        assert_eq!(MediaType::parse(""), None);
+        assert_eq!(MediaType::parse(" ; charset=utf-8"), None);
+    }
+}
@@ -1,17 +1,56 @@
+mod anomaly;
 mod body;
+mod byte_range;
+#[cfg(feature = "compression")]
+mod compression;
+mod connection;
+mod diff;
+#[cfg(feature = "encoding")]
+mod encoding;
+mod forwarded;
 mod headers;
+#[cfg(feature = "http-interop")]
+mod http_interop;
+#[cfg(feature = "json")]
+mod json;
+mod limits;
+mod media_type;
 mod parsed_request;
+mod parsed_response;
 mod partial_request;
+mod partial_response;
+mod read;
 mod request;
 mod response;
+mod smuggling;
+mod standard_header;
 mod uri;
 mod version;
+mod warning;
 
-pub use body::{HttpBody, PossibleHttpBody};
-pub use headers::HttpHeader;
+pub use anomaly::{Anomaly, AnomalyKind};
+pub use body::{BodyState, HttpBody, PossibleHttpBody};
+pub use byte_range::ByteRange;
+pub use connection::HOP_BY_HOP_HEADERS;
+pub use diff::RequestDiff;
+pub use forwarded::ForwardedElement;
+pub(crate) use headers::parse_headers;
+pub use headers::{HttpHeader, headers_equivalent};
+pub use limits::ParseLimits;
+pub use media_type::{AcceptRange, MediaType};
 pub use parsed_request::ParsedHttpRequest;
-pub use partial_request::PartialHttpRequest;
-pub use request::{HttpMethod, HttpRequest};
-pub use response::{HttpResponse, HttpStatusCode};
+pub use parsed_response::ParsedHttpResponse;
+pub(crate) use partial_request::looks_like_uri;
+pub use partial_request::{
+    DEFAULT_COMMENT_PREFIXES, FirstLineParts, FirstLineShape, PartialHttpRequest,
+    parse_first_line_lenient,
+};
+pub use partial_response::PartialHttpResponse;
+pub use read::RequestParser;
+pub use request::{HttpMethod, HttpRequest, SENSITIVE_HEADERS};
+pub use response::{HttpResponse, HttpResponseBuilder, HttpStatusCode};
+pub use smuggling::{SmugglingRisk, SmugglingRiskKind};
+pub use standard_header::StandardHeader;
 pub use uri::Uri;
 pub use version::HttpVersion;
+pub use warning::{Warning, WarningKind};
@@ -3,7 +3,15 @@ use std::ops::Range;
 
 use crate::{
     error::Error,
-    span::{Span, get_line_spans},
+    models::{
+        BodyState, HttpHeader, HttpVersion, ParseLimits, Warning, WarningKind,
+        headers::{find_header, parse_folded_header},
+    },
+    span::{
+        LineColSpan, LineKind, RequestSpans, SectionSizes, Span, bom_len, first_line_span,
+        get_line_spans, header_key_span, header_value_span, is_blank_line, offset_span,
+        trim_line_ending,
+    },
 };
 
 /// A partial HTTP request that might not conform to HTTP spec
@@ -17,6 +25,7 @@ pub struct ParsedHttpRequest<'http_message> {
     http_version: Range<usize>,
     headers: Vec<Range<usize>>,
     body: Option<Range<usize>>,
+    used_obs_fold: bool,
 }
 
 impl<'http_message> fmt::Display for ParsedHttpRequest<'http_message> {
@@ -27,7 +36,102 @@ impl<'http_message> fmt::Display for ParsedHttpRequest<'http_message> {
 
 impl<'http_message> ParsedHttpRequest<'http_message> {
     pub fn parse(message: &'http_message str) -> Result<Self, Error> {
-        parse_request(message, parse_first_line)
+        parse_request(message, parse_first_line, &ParseLimits::default())
+    }
+
+    /// Parse the first request in input, returning it with the number of bytes it occupies
+    ///
+    /// The request ends after the blank line plus a body of `Content-Length` bytes if
+    /// defined. Anything after that, like a pipelined request, is left unparsed.
+    ///
+    /// Returns [Error::UnexpectedEof] if input ends before the whole body,
+    /// [Error::InvalidBody] if the body ends part way through a character, and
+    /// [Error::UnsupportedTransferEncoding] if the body uses a `Transfer-Encoding`.
+    pub fn parse_prefix(input: &'http_message str) -> Result<(Self, usize), Error> {
+        let line_spans = get_line_spans(input);
+
+        let head_end = line_spans
+            .iter()
+            .skip(1)
+            .find(|span| is_blank_line(input, span))
+            .or(line_spans.first())
+            .map(|span| span.end)
+            .ok_or(Error::EmptyHttpMessage)?;
+
+        let head = Self::parse(&input[..head_end])?;
+
+        let headers: Vec<HttpHeader> = head
+            .header_strs()
+            .into_iter()
+            .filter_map(|line| parse_folded_header(line).ok())
+            .collect();
+
+        if let Some(header) = find_header(&headers, "Transfer-Encoding") {
+            return Err(Error::UnsupportedTransferEncoding {
+                encoding: header.value().to_string(),
+            });
+        }
+
+        let content_length = match find_header(&headers, "Content-Length") {
+            Some(header) => header
+                .value()
+                .parse::<usize>()
+                .map_err(|_| Error::invalid_header(header.key()))?,
+            None => 0,
+        };
+
+        let consumed = head_end
+            .checked_add(content_length)
+            .filter(|consumed| *consumed <= input.len())
+            .ok_or(Error::UnexpectedEof {
+                expected: content_length,
+                actual: input.len() - head_end,
+            })?;
+
+        if !input.is_char_boundary(consumed) {
+            return Err(Error::InvalidBody);
+        }
+
+        Ok((Self::parse(&input[..consumed])?, consumed))
+    }
+
+    /// Parse a request, returning [Error::LimitExceeded] if the message exceeds any limit
+    pub fn from_str_with_limits(
+        message: &'http_message str,
+        limits: ParseLimits,
+    ) -> Result<Self, Error> {
+        parse_request(message, parse_first_line, &limits)
+    }
+
+    /// Parse a request, returning [Error::MethodNotAllowed] if the method isn't allowed
+    ///
+    /// Methods are compared exactly since they're case-sensitive.
+    pub fn from_str_with_allowed_methods(
+        message: &'http_message str,
+        allowed: &[&str],
+    ) -> Result<Self, Error> {
+        let parsed = Self::parse(message)?;
+
+        if !allowed.contains(&parsed.method_str()) {
+            return Err(Error::MethodNotAllowed {
+                method: parsed.method_str().to_string(),
+                span: parsed.method.clone(),
+            });
+        }
+
+        Ok(parsed)
+    }
+
+    /// Parse a request, also returning non-fatal quality warnings
+    ///
+    /// Warnings are sorted by span start.
+    pub fn from_str_with_warnings(
+        message: &'http_message str,
+    ) -> Result<(Self, Vec<Warning>), Error> {
+        let parsed = Self::parse(message)?;
+        let warnings = parsed.warnings();
+
+        Ok((parsed, warnings))
     }
 
     pub fn parsed(
@@ -38,6 +142,8 @@ impl<'http_message> ParsedHttpRequest<'http_message> {
         headers: Vec<Range<usize>>,
         body: Option<Range<usize>>,
     ) -> Self {
+        let used_obs_fold = headers.iter().any(|span| is_folded(message, span));
+
         let partial = Self {
             message,
             method,
@@ -45,6 +151,7 @@ impl<'http_message> ParsedHttpRequest<'http_message> {
             http_version,
             headers,
             body,
+            used_obs_fold,
         };
 
         partial.verify_spans();
@@ -56,44 +163,51 @@ impl<'http_message> ParsedHttpRequest<'http_message> {
     ///
     /// - Aren't out of bounds of the message
     /// - Parts aren't overlapping or out of order
+    ///
+    /// Parsing only creates valid spans so these are debug assertions.
     fn verify_spans(&self) {
         {
-            assert!(self.method.start < self.method.end);
+            debug_assert!(self.method.start < self.method.end);
             assert_text_span(self.message(), &self.method);
         };
 
         {
-            assert!(self.uri.start < self.uri.end);
+            debug_assert!(self.uri.start < self.uri.end);
             assert_text_span(self.message(), &self.uri);
 
-            if !(self.method.start < self.uri.start && self.method.end < self.uri.start) {
-                panic!(
-                    "uri {:?} and method {:?} spans conflict",
-                    self.uri, self.method
-                );
-            }
+            debug_assert!(
+                self.method.start < self.uri.start && self.method.end < self.uri.start,
+                "uri {:?} and method {:?} spans conflict",
+                self.uri,
+                self.method
+            );
         };
 
-        {
-            assert!(self.http_version.start < self.http_version.end);
+        if self.is_http_09() {
+            debug_assert_eq!(
+                self.http_version,
+                self.uri.end..self.uri.end,
+                "http/0.9 version span must be empty and directly after the uri"
+            );
+        } else {
+            debug_assert!(self.http_version.start < self.http_version.end);
             assert_text_span(self.message(), &self.http_version);
 
-            if !(self.uri.start < self.http_version.start && self.uri.end < self.http_version.start)
-            {
-                panic!(
-                    "http version {:?} and uri {:?} spans conflict",
-                    self.http_version, self.uri
-                );
-            }
+            debug_assert!(
+                self.uri.start < self.http_version.start && self.uri.end < self.http_version.start,
+                "http version {:?} and uri {:?} spans conflict",
+                self.http_version,
+                self.uri
+            );
         };
 
         for span in self.header_spans().iter() {
-            assert!(span.start < span.end);
+            debug_assert!(span.start < span.end);
             assert_text_span(self.message(), span);
         }
 
         self.body.as_ref().inspect(|span| {
-            assert!(
+            debug_assert!(
                 span.start <= span.end,
                 "body span {:?} is not contained within message {}",
                 span,
@@ -108,6 +222,74 @@ impl<'http_message> ParsedHttpRequest<'http_message> {
         self.message
     }
 
+    /// Get the text span of the request line without its line ending
+    pub fn request_line_span(&self) -> Range<usize> {
+        first_line_span(self.message).unwrap_or_default()
+    }
+
+    /// Get the string text of the request line without its line ending
+    pub fn request_line_str(&self) -> &str {
+        self.slice_message(&self.request_line_span())
+    }
+
+    /// Get the text spans of the whitespace between request line tokens
+    ///
+    /// Gaps can be longer than one character when the request line has irregular spacing.
+    pub fn request_line_gaps(&self) -> Vec<Range<usize>> {
+        let mut gaps = Vec::with_capacity(2);
+        gaps.push(self.method.end..self.uri.start);
+
+        if !self.is_http_09() {
+            gaps.push(self.uri.end..self.http_version.start);
+        }
+
+        gaps
+    }
+
+    /// Get the request line with each gap between tokens collapsed to a single space
+    ///
+    /// The original message is unchanged.
+    pub fn normalized_request_line(&self) -> String {
+        let line = self.request_line_span();
+        let mut normalized = String::new();
+        let mut last_end = line.start;
+
+        for gap in self.request_line_gaps() {
+            normalized.push_str(&self.message[last_end..gap.start]);
+            normalized.push(' ');
+            last_end = gap.end;
+        }
+
+        normalized.push_str(&self.message[last_end..line.end]);
+        normalized
+    }
+
+    /// Get the original message with only the method replaced
+    ///
+    /// All other bytes are kept identical.
+    pub fn replace_method(&self, new: &str) -> String {
+        self.replace_span(&self.method, new)
+    }
+
+    /// Get the original message with only the uri replaced
+    ///
+    /// All other bytes are kept identical.
+    pub fn replace_uri(&self, new: &str) -> String {
+        self.replace_span(&self.uri, new)
+    }
+
+    /// Get the original message with only the http version replaced
+    ///
+    /// All other bytes are kept identical. Simple requests without a version get
+    /// a single space inserted before the new version.
+    pub fn replace_version(&self, new: &str) -> String {
+        if self.is_http_09() && !new.is_empty() {
+            return self.replace_span(&self.http_version, &format!(" {new}"));
+        }
+
+        self.replace_span(&self.http_version, new)
+    }
+
     /// Get the text span of the uri, if defined
     pub fn uri_span(&self) -> &Range<usize> {
         &self.uri
@@ -118,6 +300,43 @@ impl<'http_message> ParsedHttpRequest<'http_message> {
         self.slice_message(&self.uri)
     }
 
+    /// Get the `(key, value)` text spans of each query parameter in the uri
+    ///
+    /// Spans cover the raw text without decoding. A parameter without `=` like
+    /// `?flag` has an empty value span after its key.
+    pub fn query_param_spans(&self) -> Vec<(Range<usize>, Range<usize>)> {
+        let uri = self.uri_str();
+
+        let Some(query_start) = uri.find('?').map(|idx| self.uri.start + idx + 1) else {
+            return vec![];
+        };
+
+        let query_end = self.uri.start + uri.find('#').unwrap_or(uri.len());
+
+        let mut params = vec![];
+        let mut param_start = query_start;
+
+        for param in self.message[query_start..query_end.max(query_start)].split('&') {
+            let param_end = param_start + param.len();
+
+            if !param.is_empty() {
+                let (key, value) = match param.find('=') {
+                    Some(idx) => (
+                        param_start..param_start + idx,
+                        param_start + idx + 1..param_end,
+                    ),
+                    None => (param_start..param_end, param_end..param_end),
+                };
+
+                params.push((key, value));
+            }
+
+            param_start = param_end + 1;
+        }
+
+        params
+    }
+
     /// Get the text span of the method, if defined
     pub fn method_span(&self) -> &Range<usize> {
         &self.method
@@ -133,16 +352,64 @@ impl<'http_message> ParsedHttpRequest<'http_message> {
         &self.http_version
     }
 
-    /// Get the string text of the http version, if defined
+    /// Get the string text of the http version
+    ///
+    /// This is `HTTP/0.9` for simple requests without a version.
     pub fn http_version_str(&self) -> &str {
+        if self.is_http_09() {
+            return HTTP_09;
+        }
+
         self.slice_message(&self.http_version)
     }
 
+    /// Check if this is an HTTP/0.9 simple request (e.g. `GET /path`)
+    ///
+    /// Simple requests have an empty http version span directly after the uri.
+    pub fn is_http_09(&self) -> bool {
+        self.http_version.start == self.http_version.end
+    }
+
     /// Get a list of the header line text spans
+    ///
+    /// A header folded over multiple lines has one span covering all of them.
     pub fn header_spans(&self) -> &Vec<Range<usize>> {
         &self.headers
     }
 
+    /// Check if any header uses obsolete line folding
+    pub fn used_obs_fold(&self) -> bool {
+        self.used_obs_fold
+    }
+
+    /// Get the text spans of headers folded over multiple lines
+    pub fn folded_header_spans(&self) -> Vec<Range<usize>> {
+        self.headers
+            .iter()
+            .filter(|span| is_folded(self.message, span))
+            .cloned()
+            .collect()
+    }
+
+    /// Get each header key in order, including duplicates
+    pub fn header_keys(&self) -> Vec<&str> {
+        self.headers
+            .iter()
+            .map(|span| self.slice_message(&header_key_span(self.message, span)))
+            .collect()
+    }
+
+    /// Get the byte offset of the `:` in each header line
+    ///
+    /// Header lines without a colon are skipped.
+    pub fn header_colon_offsets(&self) -> Vec<usize> {
+        self.headers
+            .iter()
+            .map(|span| header_key_span(self.message, span).end)
+            .filter(|offset| self.message[*offset..].starts_with(':'))
+            .collect()
+    }
+
     /// Get a list of the string text header lines
     pub fn header_strs(&self) -> Vec<&str> {
         self.headers
@@ -163,22 +430,322 @@ impl<'http_message> ParsedHttpRequest<'http_message> {
         self.header_span(key).map(|span| self.slice_message(span))
     }
 
-    /// Get the string text of the body, if defined
+    /// Get the text span of a header value by key, if defined
+    ///
+    /// The span excludes surrounding whitespace and the line ending. An empty
+    /// value has an empty span positioned after the colon and any whitespace.
+    pub fn header_value_span(&self, key: &str) -> Option<Range<usize>> {
+        self.header_span(key)
+            .and_then(|span| header_value_span(self.message, span))
+    }
+
+    /// Get the trimmed string text and text span of a header value by key, if defined
+    pub fn header_value(&self, key: &str) -> Option<(&str, Range<usize>)> {
+        self.header_value_span(key)
+            .map(|span| (self.slice_message(&span), span))
+    }
+
+    /// Get the raw string text of the body, if defined
+    ///
+    /// This is exactly the text after the separator line, including any trailing
+    /// line terminators. See [ParsedHttpRequest::body_trimmed] to exclude them.
     pub fn body_str(&self) -> Option<&str> {
         self.body.as_ref().map(|span| &self.message[span.clone()])
     }
 
+    /// Get the string text of the body without trailing `\r` and `\n`, if defined
+    pub fn body_trimmed(&self) -> Option<&str> {
+        self.body_str()
+            .map(|body| body.trim_end_matches(['\r', '\n']))
+    }
+
+    /// Get the text span of the exact body bytes after the separator line
+    ///
+    /// Unlike [ParsedHttpRequest::body_str] this is defined for an empty body, as an
+    /// empty span directly after the `\r\n` or `\n` separator. Simple requests have
+    /// no separator and no body span.
+    pub fn raw_body_span(&self) -> Option<Range<usize>> {
+        if self.is_http_09() {
+            return None;
+        }
+
+        let separator = get_line_spans(self.message)
+            .into_iter()
+            .skip(1)
+            .find(|span| is_blank_line(self.message, span))?;
+
+        Some(separator.end..self.message.len())
+    }
+
+    /// Get whether the body is absent, empty, or present
+    ///
+    /// The body is empty when there's a separator line with nothing after it, and
+    /// absent when there's no separator like in a simple request.
+    pub fn body_state(&self) -> BodyState {
+        match self.raw_body_span() {
+            None => BodyState::Absent,
+            Some(span) if span.is_empty() => BodyState::Empty,
+            Some(_) => BodyState::Present,
+        }
+    }
+
+    /// Get owned copies of the head and body
+    ///
+    /// The head is the request line through the blank separator line inclusive and
+    /// the body is everything after it. The parsed request is kept.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn into_head_and_body(&self) -> (String, Option<String>) {
+        let head_end = self
+            .body
+            .as_ref()
+            .map_or(self.message.len(), |body| body.start);
+
+        (
+            self.message[..head_end].to_string(),
+            self.body_str().map(str::to_string),
+        )
+    }
+
+    /// Get the line and column span of the method
+    pub fn method_linecol(&self) -> LineColSpan {
+        LineColSpan::from_span(self.message, &self.method)
+    }
+
+    /// Get the line and column span of the uri
+    pub fn uri_linecol(&self) -> LineColSpan {
+        LineColSpan::from_span(self.message, &self.uri)
+    }
+
+    /// Get the line and column span of the http version
+    pub fn http_version_linecol(&self) -> LineColSpan {
+        LineColSpan::from_span(self.message, &self.http_version)
+    }
+
+    /// Get the line and column spans of the header lines
+    pub fn header_linecols(&self) -> Vec<LineColSpan> {
+        self.headers
+            .iter()
+            .map(|span| LineColSpan::from_span(self.message, span))
+            .collect()
+    }
+
+    /// Get the line and column span of the body, if defined
+    pub fn body_linecol(&self) -> Option<LineColSpan> {
+        self.body
+            .as_ref()
+            .map(|span| LineColSpan::from_span(self.message, span))
+    }
+
+    /// Check that no header-looking lines appear in the body
+    ///
+    /// A `key: value` line after the separator usually means a header leaked in to
+    /// the body. The error span points at the first one without its line ending.
+    pub fn validate_structure(&self) -> Result<(), Error> {
+        let Some(body) = &self.body else {
+            return Ok(());
+        };
+
+        let leaked_header = get_line_spans(&self.message[body.clone()])
+            .into_iter()
+            .map(|line| trim_line_ending(self.message, &offset_span(line, body.start)))
+            .find(|line| looks_like_header(self.slice_message(line)));
+
+        match leaked_header {
+            Some(span) => Err(Error::HeaderAfterBody { span }),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that HTTP/1.1 and later requests have a `Host` header
+    ///
+    /// Returns [Error::MissingHost] if it's missing. HTTP/1.0, HTTP/0.9, and
+    /// unparsable versions are skipped.
+    pub fn validate_host(&self) -> Result<(), Error> {
+        let requires_host = HttpVersion::from(self.http_version_str())
+            .major_minor()
+            .is_some_and(|version| version >= (1, 1));
+
+        let has_host = self.headers.iter().any(|span| {
+            self.slice_message(&header_key_span(self.message, span))
+                .eq_ignore_ascii_case("Host")
+        });
+
+        if requires_host && !has_host {
+            return Err(Error::MissingHost);
+        }
+
+        Ok(())
+    }
+
+    /// Check that the declared `Content-Length` matches the body length in bytes
+    ///
+    /// The check is skipped without a `Content-Length` header or a body. The header
+    /// key is matched ignoring ASCII case.
+    pub fn verify_content_length(&self) -> Result<(), Error> {
+        let Some(body) = &self.body else {
+            return Ok(());
+        };
+
+        let Some(value) = self
+            .headers
+            .iter()
+            .find(|span| {
+                self.slice_message(&header_key_span(self.message, span))
+                    .eq_ignore_ascii_case("Content-Length")
+            })
+            .and_then(|span| header_value_span(self.message, span))
+        else {
+            return Ok(());
+        };
+
+        let declared = self
+            .slice_message(&value)
+            .parse()
+            .map_err(|_| Error::invalid_header("Content-Length"))?;
+
+        if declared != body.len() {
+            return Err(Error::BodyLengthMismatch {
+                declared,
+                actual: body.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Get the text spans of each message region in order with their kind
+    ///
+    /// Request line, header, and separator spans include their line endings. The body
+    /// is a single span covering all remaining lines.
+    pub fn classified_lines(&self) -> impl Iterator<Item = (Range<usize>, LineKind)> {
+        let line_spans = get_line_spans(self.message);
+
+        let mut lines = vec![];
+
+        if let Some(first_line) = line_spans.first() {
+            let start = bom_len(self.message).min(first_line.end);
+            lines.push((start..first_line.end, LineKind::RequestLine));
+        }
+
+        for header in &self.headers {
+            lines.push((header.clone(), LineKind::Header));
+        }
+
+        if !self.is_http_09()
+            && let Some(separator) = line_spans
+                .iter()
+                .find(|span| is_blank_line(self.message, span))
+        {
+            lines.push((separator.clone(), LineKind::Separator));
+        }
+
+        if let Some(body) = &self.body {
+            lines.push((body.clone(), LineKind::Body));
+        }
+
+        lines.into_iter()
+    }
+
+    /// Get the text span of the whole message from the start through the body or separator
+    ///
+    /// For a request from [ParsedHttpRequest::parse_prefix] the end is where the
+    /// next pipelined request starts.
+    pub fn message_span(&self) -> Range<usize> {
+        let end = self
+            .classified_lines()
+            .map(|(span, _)| span.end)
+            .max()
+            .unwrap_or(0);
+
+        0..end
+    }
+
+    /// Get the size in bytes of the request line, headers, and body
+    ///
+    /// A missing body has a size of `0`.
+    pub fn section_sizes(&self) -> SectionSizes {
+        let mut sizes = SectionSizes::default();
+
+        for (span, kind) in self.classified_lines() {
+            match kind {
+                LineKind::RequestLine => sizes.request_line += span.len(),
+                LineKind::Header => sizes.headers += span.len(),
+                LineKind::Body => sizes.body += span.len(),
+                LineKind::Separator => {}
+            }
+        }
+
+        sizes
+    }
+
+    /// Get all the text spans
+    pub fn spans(&self) -> RequestSpans {
+        RequestSpans {
+            method: self.method.clone(),
+            uri: self.uri.clone(),
+            http_version: self.http_version.clone(),
+            headers: self.headers.clone(),
+            body: self.body.clone(),
+        }
+    }
+
+    fn warnings(&self) -> Vec<Warning> {
+        let mut warnings = vec![];
+
+        for gap in self.request_line_gaps() {
+            if gap.len() > 1 {
+                warnings.push(Warning::new(
+                    gap.clone(),
+                    WarningKind::ExtraWhitespace,
+                    format!("{} whitespace characters in the request line", gap.len()),
+                ));
+            }
+        }
+
+        let version = self.slice_message(&self.http_version);
+
+        if version != version.to_ascii_uppercase() {
+            warnings.push(Warning::new(
+                self.http_version.clone(),
+                WarningKind::LowercaseVersion,
+                format!("http version {version:?} should be uppercase"),
+            ));
+        }
+
+        for span in self.folded_header_spans() {
+            warnings.push(Warning::new(
+                span,
+                WarningKind::ObsoleteLineFolding,
+                "header is folded over multiple lines".to_string(),
+            ));
+        }
+
+        warnings.sort_by_key(|warning| warning.span.start);
+
+        warnings
+    }
+
     /// Return a slice of the message string
     fn slice_message(&self, span: &Span) -> &str {
         &self.message[span.clone()]
     }
+
+    fn replace_span(&self, span: &Span, new: &str) -> String {
+        let mut message = self.message.to_string();
+        message.replace_range(span.clone(), new);
+        message
+    }
 }
 
 fn assert_text_span(text: &str, span: &Range<usize>) {
-    text.get(span.clone())
-        .unwrap_or_else(|| panic!("span {span:?} is outside of text bounds"));
+    debug_assert!(
+        text.get(span.clone()).is_some(),
+        "span {span:?} is outside of text bounds"
+    );
 }
 
+const HTTP_09: &str = "HTTP/0.9";
+
 impl<'http_message> Default for ParsedHttpRequest<'http_message> {
     fn default() -> Self {
         Self::parse("GET https://example.com HTTP/1.1\n\n").unwrap()
@@ -194,31 +761,80 @@ type FirstLineParts = (
 fn parse_request<'http_message, F>(
     input: &'http_message str,
     parse_first_line: F,
+    limits: &ParseLimits,
 ) -> Result<ParsedHttpRequest<'http_message>, Error>
 where
     F: Fn(&str) -> FirstLineParts,
 {
-    if input.trim().is_empty() {
+    if input.len() > limits.max_total {
+        return Err(Error::LimitExceeded { which: "max_total" });
+    }
+
+    let bom_len = bom_len(input);
+
+    if input[bom_len..].trim().is_empty() {
         return Err(Error::EmptyHttpMessage);
     }
 
     let line_spans = get_line_spans(input);
 
-    let first_empty_line_idx = line_spans
-        .iter()
-        .position(|span| span.len() == 1)
-        .expect("should have at least one empty line in HTTP request");
+    let first_line = line_spans.first().ok_or(Error::EmptyHttpMessage)?;
+
+    check_line_len(input, first_line, limits)?;
+
+    let (method, uri, http_version) = parse_first_line(&input[bom_len..first_line.end]);
+
+    let method = method
+        .map(|span| offset_span(span, bom_len))
+        .ok_or(Error::missing_required("method"))?;
+    let uri = uri
+        .map(|span| offset_span(span, bom_len))
+        .ok_or(Error::missing_required("uri"))?;
 
-    let first_line = line_spans.first().unwrap();
+    // A request line without a version is an HTTP/0.9 simple request with no headers or body,
+    // so anything after it means the version is missing rather than being dropped
+    let Some(http_version) = http_version else {
+        if !input[first_line.end..].trim().is_empty() {
+            return Err(Error::missing_required("http_version"));
+        }
+
+        let http_version = uri.end..uri.end;
+
+        return Ok(ParsedHttpRequest::parsed(
+            input,
+            method,
+            uri,
+            http_version,
+            vec![],
+            None,
+        ));
+    };
+
+    let http_version = offset_span(http_version, bom_len);
+
+    let mut first_empty_line_idx = None;
 
-    let (method, uri, http_version) = parse_first_line(&input[first_line.clone()]);
+    for (idx, span) in line_spans.iter().enumerate().skip(1) {
+        if is_blank_line(input, span) {
+            first_empty_line_idx = Some(idx);
+            break;
+        }
+
+        if idx > limits.max_headers {
+            return Err(Error::LimitExceeded {
+                which: "max_headers",
+            });
+        }
+
+        check_line_len(input, span, limits)?;
+    }
 
-    let method = method.unwrap();
-    let uri = uri.unwrap();
-    let http_version = http_version.unwrap();
+    let first_empty_line_idx = first_empty_line_idx.ok_or(Error::missing_required("empty line"))?;
 
     let (header_spans, body_spans) = get_header_and_body_spans(line_spans, first_empty_line_idx);
 
+    let header_spans = fold_header_spans(input, header_spans);
+
     let body_span = get_span_extent_from_spans(body_spans);
 
     Ok(ParsedHttpRequest::parsed(
@@ -231,6 +847,27 @@ where
     ))
 }
 
+/// Check if a line is a `key: value` header with a token key
+fn looks_like_header(line: &str) -> bool {
+    line.split_once(':').is_some_and(|(key, _)| {
+        !key.is_empty()
+            && key
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte))
+    })
+}
+
+/// Check a head line without its line ending is within the line length limit
+fn check_line_len(input: &str, line: &Span, limits: &ParseLimits) -> Result<(), Error> {
+    if trim_line_ending(input, line).len() > limits.max_line_len {
+        return Err(Error::LimitExceeded {
+            which: "max_line_len",
+        });
+    }
+
+    Ok(())
+}
+
 /// Parse the first line of an HTTP request message
 fn parse_first_line(first_line: &str) -> FirstLineParts {
     let mut parts = vec![];
@@ -241,7 +878,7 @@ fn parse_first_line(first_line: &str) -> FirstLineParts {
             if i > last_end {
                 parts.push(last_end..i);
             }
-            last_end = i + 1;
+            last_end = i + c.len_utf8();
         }
     }
 
@@ -266,16 +903,35 @@ fn get_header_and_body_spans(
     (header_spans, body_spans)
 }
 
-fn get_span_extent_from_spans(body_spans: Option<Vec<Range<usize>>>) -> Option<Range<usize>> {
-    let body_span = body_spans.and_then(|spans| {
-        if spans.is_empty() {
-            return None;
+/// Merge continuation lines starting with a space or tab in to the previous header span
+///
+/// A continuation line without a previous header keeps its own span.
+fn fold_header_spans(input: &str, header_spans: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    let mut folded: Vec<Range<usize>> = vec![];
+
+    for span in header_spans {
+        match folded.last_mut() {
+            Some(last) if input[span.clone()].starts_with([' ', '\t']) => last.end = span.end,
+            _ => folded.push(span),
         }
+    }
+
+    folded
+}
 
-        let first = spans.first().unwrap();
-        let last = spans.last().unwrap();
+/// Check if a header span covers more than one line
+fn is_folded(input: &str, span: &Range<usize>) -> bool {
+    input[trim_line_ending(input, span)].contains('\n')
+}
+
+/// Get the body span from the separator line through the last line
+///
+/// The body starts after the full separator line so `\r\n` separators are excluded.
+fn get_span_extent_from_spans(body_spans: Option<Vec<Range<usize>>>) -> Option<Range<usize>> {
+    let body_span = body_spans.and_then(|spans| {
+        let (separator, last) = (spans.first()?, spans.last()?);
 
-        Some(first.start + 1..last.end)
+        Some(separator.end..last.end)
     });
 
     if let Some(body_span) = &body_span
@@ -288,94 +944,797 @@ fn get_span_extent_from_spans(body_spans: Option<Vec<Range<usize>>>) -> Option<R
 }
 
 #[cfg(test)]
+#[allow(clippy::reversed_empty_ranges, clippy::single_range_in_vec_init)]
 mod tests {
-    use crate::models::{HttpRequest, ParsedHttpRequest};
+    use crate::{
+        error::Error,
+        models::{
+            BodyState, HttpHeader, HttpRequest, ParseLimits, ParsedHttpRequest, Warning,
+            WarningKind,
+        },
+        span::{LineColSpan, LineKind, RequestSpans, SectionSizes},
+    };
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_out_of_bounds_method_span() {
         ParsedHttpRequest::parsed("", 1..2, 0..0, 0..0, vec![], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_inverted_method_span() {
         ParsedHttpRequest::parsed("", 2..1, 0..0, 0..0, vec![], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_out_of_bounds_uri_span() {
         ParsedHttpRequest::parsed("", 0..0, 1..2, 0..0, vec![], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_inverted_uri_span() {
         ParsedHttpRequest::parsed("", 0..0, 2..1, 0..0, vec![], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_method_span_overlaps_uri_span() {
         ParsedHttpRequest::parsed("GET https://example.com", 0..3, 2..10, 0..0, vec![], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_out_of_bounds_http_version_span() {
         ParsedHttpRequest::parsed("", 0..0, 0..0, 1..2, vec![], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_inverted_http_version_span() {
         ParsedHttpRequest::parsed("", 0..0, 0..0, 2..1, vec![], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_out_of_bounds_header_span() {
         ParsedHttpRequest::parsed("", 0..0, 0..0, 0..0, vec![1..2], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_inverted_header_span() {
         ParsedHttpRequest::parsed("", 0..0, 0..0, 0..0, vec![2..1], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_out_of_bounds_body_span() {
         ParsedHttpRequest::parsed("", 0..0, 0..0, 0..0, vec![], Some(1..2));
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_inverted_body_span() {
         ParsedHttpRequest::parsed("", 0..0, 0..0, 0..0, vec![], Some(2..1));
     }
 
     #[test]
-    fn implements_default() {
-        let parsed = ParsedHttpRequest::default();
+    fn skips_leading_bom() {
+        let parsed =
+            ParsedHttpRequest::parse("\u{FEFF}GET https://example.com HTTP/1.1\n\n").unwrap();
+
+        assert_eq!(&(3..6), parsed.method_span());
+        assert_eq!("GET", parsed.method_str());
+        assert_eq!("https://example.com", parsed.uri_str());
+        assert_eq!("HTTP/1.1", parsed.http_version_str());
+    }
+
+    #[test]
+    fn parses_http_09_simple_request() {
+        let parsed = ParsedHttpRequest::parse("GET /path\r\n").unwrap();
 
         assert_eq!(
-            ParsedHttpRequest::parsed(
-                "GET https://example.com HTTP/1.1\n\n",
-                0..3,
-                4..23,
-                24..32,
-                vec![],
-                None
-            ),
+            ParsedHttpRequest::parsed("GET /path\r\n", 0..3, 4..9, 9..9, vec![], None),
             parsed
         );
 
-        assert_eq!("GET https://example.com HTTP/1.1\n\n", parsed.message());
+        assert!(parsed.is_http_09());
+        assert_eq!("GET", parsed.method_str());
+        assert_eq!("/path", parsed.uri_str());
+        assert_eq!("HTTP/0.9", parsed.http_version_str());
+    }
 
-        let request: HttpRequest = parsed.into();
+    #[test]
+    fn parses_http_09_simple_request_with_remaining_lines_to_error() {
+        assert_eq!(
+            Err(Error::missing_required("http_version")),
+            ParsedHttpRequest::parse("GET /path\nx-key: 123\n\nbody")
+        );
+        assert_eq!(
+            Err(Error::missing_required("http_version")),
+            ParsedHttpRequest::parse("GET /path\n\nbody")
+        );
+        assert!(
+            ParsedHttpRequest::parse("GET /path\r\n\r\n")
+                .unwrap()
+                .is_http_09()
+        );
+    }
+
+    #[test]
+    fn parses_versioned_request_as_not_http_09() {
+        assert!(!ParsedHttpRequest::default().is_http_09());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn verifies_misplaced_http_09_version_span() {
+        ParsedHttpRequest::parsed("GET /path", 0..3, 4..9, 2..2, vec![], None);
+    }
+
+    #[test]
+    fn returns_header_value_and_span() {
+        let parsed = ParsedHttpRequest::parse(
+            "GET https://example.com HTTP/1.1\nx-key:  123 \nX-Empty:\n\n",
+        )
+        .unwrap();
+
+        assert_eq!(Some(("123", 41..44)), parsed.header_value("x-key"));
+        assert_eq!(Some(("", 54..54)), parsed.header_value("X-Empty"));
+        assert_eq!(None, parsed.header_value("x-missing"));
+    }
+
+    #[test]
+    fn returns_header_keys() {
+        let parsed = ParsedHttpRequest::parse(
+            "GET https://example.com HTTP/1.1\r\nAccept: */*\r\nx-key: 1\r\nx-key: 2\r\n\r\n",
+        )
+        .unwrap();
+
+        assert_eq!(vec!["Accept", "x-key", "x-key"], parsed.header_keys());
+    }
+
+    #[test]
+    fn returns_header_colon_offsets() {
+        let message = "GET /path HTTP/1.1\nAccept: */*\ninvalid\nx-longer-key:a:b\n\n";
+        let parsed = ParsedHttpRequest::parse(message).unwrap();
+
+        let offsets = parsed.header_colon_offsets();
+
+        assert_eq!(vec![25, 51], offsets);
+        assert!(
+            offsets
+                .iter()
+                .all(|offset| &message[*offset..*offset + 1] == ":")
+        );
+    }
+
+    #[test]
+    fn returns_header_value_without_trailing_whitespace() {
+        let parsed = ParsedHttpRequest::parse(
+            "GET https://example.com HTTP/1.1\r\nx-key: value  \r\nx-tab: value\t \t\r\n\r\n",
+        )
+        .unwrap();
+
+        assert_eq!(Some(("value", 41..46)), parsed.header_value("x-key"));
+        assert_eq!(Some(("value", 57..62)), parsed.header_value("x-tab"));
+    }
+
+    #[test]
+    fn parses_crlf_body_with_blank_lines() {
+        let parsed = ParsedHttpRequest::parse(
+            "POST https://example.com HTTP/1.1\r\nx-key: 123\r\n\r\nfirst\r\n\r\nsecond\r\n",
+        )
+        .unwrap();
+
+        assert_eq!(vec!["x-key: 123\r\n"], parsed.header_strs());
+        assert_eq!(Some("first\r\n\r\nsecond\r\n"), parsed.body_str());
+    }
+
+    #[test]
+    fn returns_request_line() {
+        let parsed =
+            ParsedHttpRequest::parse("GET https://example.com HTTP/1.1\r\nx-key: 123\r\n\r\n")
+                .unwrap();
+
+        assert_eq!(0..32, parsed.request_line_span());
+        assert_eq!(
+            "GET https://example.com HTTP/1.1",
+            parsed.request_line_str()
+        );
+    }
+
+    #[test]
+    fn returns_request_line_gaps() {
+        let parsed = ParsedHttpRequest::parse("GET  https://example.com \tHTTP/1.1\n\n").unwrap();
+
+        assert_eq!(vec![3..5, 24..26], parsed.request_line_gaps());
+
+        let parsed = ParsedHttpRequest::parse("GET   /path\n").unwrap();
+
+        assert_eq!(vec![3..6], parsed.request_line_gaps());
+    }
+
+    #[test]
+    fn returns_raw_and_trimmed_body() {
+        let parsed =
+            ParsedHttpRequest::parse("POST https://example.com HTTP/1.1\r\n\r\n{\r\n}\r\n\n")
+                .unwrap();
+
+        assert_eq!(Some("{\r\n}\r\n\n"), parsed.body_str());
+        assert_eq!(Some("{\r\n}"), parsed.body_trimmed());
+    }
+
+    #[test]
+    fn returns_raw_body_span() {
+        let message = "POST /path HTTP/1.1\r\nx-key: 1\r\n\r\n\r\nbody\r\n";
+        let parsed = ParsedHttpRequest::parse(message).unwrap();
+
+        assert_eq!(Some(33..41), parsed.raw_body_span());
+        assert_eq!("\r\nbody\r\n", &message[33..41]);
+
+        let parsed = ParsedHttpRequest::parse("POST /path HTTP/1.1\nx-key: 1\n\nbody").unwrap();
+        assert_eq!(Some(30..34), parsed.raw_body_span());
+
+        let parsed = ParsedHttpRequest::parse("POST /path HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(None, parsed.body_str());
+        assert_eq!(Some(23..23), parsed.raw_body_span());
+
+        let parsed = ParsedHttpRequest::parse("GET /path\n").unwrap();
+        assert_eq!(None, parsed.raw_body_span());
+    }
+
+    #[test]
+    fn returns_body_state() {
+        for (message, state) in [
+            ("GET /path\n", BodyState::Absent),
+            (
+                "POST /path HTTP/1.1\r\nContent-Length: 0\r\n\r\n",
+                BodyState::Empty,
+            ),
+            ("POST /path HTTP/1.1\n\n{}", BodyState::Present),
+        ] {
+            let parsed = ParsedHttpRequest::parse(message).unwrap();
+            assert_eq!(state, parsed.body_state(), "{message:?}");
+        }
+    }
+
+    #[test]
+    fn returns_head_and_body() {
+        let parsed =
+            ParsedHttpRequest::parse("POST https://example.com HTTP/1.1\r\nx-key: 123\r\n\r\n{}")
+                .unwrap();
+
+        assert_eq!(
+            (
+                "POST https://example.com HTTP/1.1\r\nx-key: 123\r\n\r\n".to_string(),
+                Some("{}".to_string())
+            ),
+            parsed.into_head_and_body()
+        );
+        assert_eq!(Some("{}"), parsed.body_str());
+
+        let parsed = ParsedHttpRequest::parse("GET https://example.com HTTP/1.1\n\n").unwrap();
+
+        assert_eq!(
+            ("GET https://example.com HTTP/1.1\n\n".to_string(), None),
+            parsed.into_head_and_body()
+        );
+    }
+
+    #[test]
+    fn returns_normalized_request_line() {
+        let message = "GET  https://example.com \tHTTP/1.1\n\n";
+        let parsed = ParsedHttpRequest::parse(message).unwrap();
+
+        assert_eq!(
+            "GET https://example.com HTTP/1.1",
+            parsed.normalized_request_line()
+        );
+        assert_eq!(message, parsed.message());
+
+        let parsed = ParsedHttpRequest::parse("GET   /path\n").unwrap();
+
+        assert_eq!("GET /path", parsed.normalized_request_line());
+    }
+
+    #[test]
+    fn replaces_request_line_tokens() {
+        let message = "GET  https://example.com \tHTTP/1.1\r\nx-key: 123\r\n\r\nbody";
+        let parsed = ParsedHttpRequest::parse(message).unwrap();
+
+        assert_eq!(
+            "POST  https://example.com \tHTTP/1.1\r\nx-key: 123\r\n\r\nbody",
+            parsed.replace_method("POST")
+        );
+        assert_eq!(
+            "GET  /path?a=1 \tHTTP/1.1\r\nx-key: 123\r\n\r\nbody",
+            parsed.replace_uri("/path?a=1")
+        );
+        assert_eq!(
+            "GET  https://example.com \tHTTP/2\r\nx-key: 123\r\n\r\nbody",
+            parsed.replace_version("HTTP/2")
+        );
+        assert_eq!(message, parsed.message());
+
+        let parsed = ParsedHttpRequest::parse("GET /path\n").unwrap();
+
+        assert_eq!("GET /path HTTP/1.0\n", parsed.replace_version("HTTP/1.0"));
+        assert_eq!("GET /path\n", parsed.replace_version(""));
+    }
+
+    #[test]
+    fn returns_line_col_spans() {
+        let parsed =
+            ParsedHttpRequest::parse("GET https://example.com HTTP/1.1\nx-key: 123\n\n{\n}")
+                .unwrap();
+
+        assert_eq!(
+            LineColSpan {
+                start: (0, 0),
+                end: (0, 3)
+            },
+            parsed.method_linecol()
+        );
+        assert_eq!(
+            LineColSpan {
+                start: (0, 4),
+                end: (0, 23)
+            },
+            parsed.uri_linecol()
+        );
+        assert_eq!(
+            LineColSpan {
+                start: (0, 24),
+                end: (0, 32)
+            },
+            parsed.http_version_linecol()
+        );
+        assert_eq!(
+            vec![LineColSpan {
+                start: (1, 0),
+                end: (2, 0)
+            }],
+            parsed.header_linecols()
+        );
+        assert_eq!(
+            Some(LineColSpan {
+                start: (3, 0),
+                end: (4, 1)
+            }),
+            parsed.body_linecol()
+        );
+    }
+
+    #[test]
+    fn returns_classified_lines() {
+        let parsed =
+            ParsedHttpRequest::parse("POST https://example.com HTTP/1.1\r\nx-key: 123\r\n\r\n{\n}")
+                .unwrap();
+
+        assert_eq!(
+            vec![
+                (0..35, LineKind::RequestLine),
+                (35..47, LineKind::Header),
+                (47..49, LineKind::Separator),
+                (49..52, LineKind::Body),
+            ],
+            parsed.classified_lines().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn parses_to_errors() {
+        assert_eq!(
+            Err(Error::missing_required("method")),
+            ParsedHttpRequest::parse("  \nx-key: 123\n\n")
+        );
+        assert_eq!(
+            Err(Error::missing_required("uri")),
+            ParsedHttpRequest::parse("GET\n\n")
+        );
+        assert_eq!(
+            Err(Error::missing_required("empty line")),
+            ParsedHttpRequest::parse("GET https://example.com HTTP/1.1\nx-key: 123")
+        );
+    }
+
+    #[test]
+    fn parses_non_ascii_whitespace_on_char_boundaries() {
+        let parsed = ParsedHttpRequest::parse("GET\u{3000}/path HTTP/1.1\n\n").unwrap();
+
+        assert_eq!("GET", parsed.method_str());
+        assert_eq!("/path", parsed.uri_str());
+    }
+
+    #[test]
+    fn validates_structure() {
+        let parsed = ParsedHttpRequest::parse(
+            "POST https://example.com HTTP/1.1\nx-key: 123\n\n{\"key\": 1}\nplain text\n",
+        )
+        .unwrap();
+
+        assert_eq!(Ok(()), parsed.validate_structure());
+
+        let parsed = ParsedHttpRequest::parse(
+            "POST https://example.com HTTP/1.1\n\nbody\r\nX-Leaked: 1\r\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Err(Error::HeaderAfterBody { span: 41..52 }),
+            parsed.validate_structure()
+        );
+    }
+
+    #[test]
+    fn returns_message_span() {
+        let parsed = ParsedHttpRequest::parse("GET /path\n").unwrap();
+        assert_eq!(0..10, parsed.message_span());
+
+        let parsed = ParsedHttpRequest::parse(
+            "\u{FEFF}GET https://example.com HTTP/1.1\r\nx-key: 1\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(0..49, parsed.message_span());
+    }
+
+    #[test]
+    fn returns_section_sizes() {
+        let parsed = ParsedHttpRequest::parse(
+            "\u{FEFF}POST /path HTTP/1.1\r\nx-key: 1\r\nx-other: 2\n\r\n{\"a\": 1}\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            SectionSizes {
+                request_line: 21,
+                headers: 21,
+                body: 9,
+            },
+            parsed.section_sizes()
+        );
+
+        let parsed = ParsedHttpRequest::parse("GET /path HTTP/1.1\n\n").unwrap();
+
+        assert_eq!(
+            SectionSizes {
+                request_line: 19,
+                headers: 0,
+                body: 0,
+            },
+            parsed.section_sizes()
+        );
+    }
+
+    #[test]
+    fn parses_prefix() {
+        let input = "POST https://example.com HTTP/1.1\ncontent-length: 2\n\n{}\
+                     GET https://example.com HTTP/1.1\n\n";
+
+        let (parsed, consumed) = ParsedHttpRequest::parse_prefix(input).unwrap();
+
+        assert_eq!(55, consumed);
+        assert_eq!(0..consumed, parsed.message_span());
+        assert_eq!("POST", parsed.method_str());
+        assert_eq!(Some("{}"), parsed.body_str());
+
+        let (parsed, consumed) = ParsedHttpRequest::parse_prefix(&input[consumed..]).unwrap();
+
+        assert_eq!(34, consumed);
+        assert_eq!(0..consumed, parsed.message_span());
+        assert_eq!("GET", parsed.method_str());
+        assert_eq!(None, parsed.body_str());
+    }
+
+    #[test]
+    fn parses_prefix_to_errors() {
+        assert_eq!(
+            Err(Error::UnexpectedEof {
+                expected: 10,
+                actual: 2
+            }),
+            ParsedHttpRequest::parse_prefix(
+                "POST https://example.com HTTP/1.1\nContent-Length: 10\n\n{}"
+            )
+        );
+        assert_eq!(
+            Err(Error::UnexpectedEof {
+                expected: usize::MAX,
+                actual: 2
+            }),
+            ParsedHttpRequest::parse_prefix(
+                "POST https://example.com HTTP/1.1\nContent-Length: 18446744073709551615\n\n{}"
+            )
+        );
+        assert_eq!(
+            Err(Error::InvalidBody),
+            ParsedHttpRequest::parse_prefix(
+                "POST https://example.com HTTP/1.1\nContent-Length: 1\n\né"
+            )
+        );
+        assert_eq!(
+            Err(Error::UnsupportedTransferEncoding {
+                encoding: "chunked".to_string()
+            }),
+            ParsedHttpRequest::parse_prefix(
+                "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\nGET /x HTTP/1.1\r\n\r\n"
+            )
+        );
+        assert_eq!(
+            Err(Error::invalid_header("Content-Length")),
+            ParsedHttpRequest::parse_prefix(
+                "POST https://example.com HTTP/1.1\nContent-Length: x\n\n"
+            )
+        );
+        assert_eq!(
+            Err(Error::missing_required("empty line")),
+            ParsedHttpRequest::parse_prefix("GET https://example.com HTTP/1.1\nx-key: 1")
+        );
+    }
+
+    #[test]
+    fn parses_within_limits() {
+        let limits = ParseLimits {
+            max_headers: 1,
+            max_line_len: 32,
+            max_total: 45,
+        };
+
+        assert!(
+            ParsedHttpRequest::from_str_with_limits(
+                "GET https://example.com HTTP/1.1\nx-key: 123\n\n",
+                limits
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn returns_limit_exceeded() {
+        let message = "GET https://example.com HTTP/1.1\na: 1\nb: 2\n\n";
+
+        let limits = ParseLimits {
+            max_total: 10,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(Error::LimitExceeded { which: "max_total" }),
+            ParsedHttpRequest::from_str_with_limits(message, limits)
+        );
+
+        let limits = ParseLimits {
+            max_headers: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(Error::LimitExceeded {
+                which: "max_headers"
+            }),
+            ParsedHttpRequest::from_str_with_limits(message, limits)
+        );
+
+        let limits = ParseLimits {
+            max_line_len: 31,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(Error::LimitExceeded {
+                which: "max_line_len"
+            }),
+            ParsedHttpRequest::from_str_with_limits(message, limits)
+        );
+    }
+
+    #[test]
+    fn returns_method_not_allowed() {
+        let message = "TRACE https://example.com HTTP/1.1\n\n";
+
+        assert_eq!(
+            Err(Error::MethodNotAllowed {
+                method: "TRACE".to_string(),
+                span: 0..5,
+            }),
+            ParsedHttpRequest::from_str_with_allowed_methods(message, &["GET", "POST"])
+        );
+
+        assert!(ParsedHttpRequest::from_str_with_allowed_methods(message, &["TRACE"]).is_ok());
+        assert!(ParsedHttpRequest::from_str_with_allowed_methods(message, &["trace"]).is_err());
+    }
+
+    #[test]
+    fn returns_warnings() {
+        let (parsed, warnings) =
+            ParsedHttpRequest::from_str_with_warnings("GET  /path http/1.1\nx-key: a\n  b\n\n")
+                .unwrap();
+
+        assert_eq!("/path", parsed.uri_str());
+        assert_eq!(
+            vec![
+                Warning::new(
+                    3..5,
+                    WarningKind::ExtraWhitespace,
+                    "2 whitespace characters in the request line".to_string()
+                ),
+                Warning::new(
+                    11..19,
+                    WarningKind::LowercaseVersion,
+                    "http version \"http/1.1\" should be uppercase".to_string()
+                ),
+                Warning::new(
+                    20..33,
+                    WarningKind::ObsoleteLineFolding,
+                    "header is folded over multiple lines".to_string()
+                ),
+            ],
+            warnings
+        );
+
+        let (_, warnings) =
+            ParsedHttpRequest::from_str_with_warnings("GET /path HTTP/1.1\nx-key: a\n\n").unwrap();
+        assert_eq!(Vec::<Warning>::new(), warnings);
+
+        assert_eq!(
+            Err(Error::missing_required("uri")),
+            ParsedHttpRequest::from_str_with_warnings("GET\n\n")
+        );
+    }
+
+    #[test]
+    fn folds_continuation_lines_in_to_header_spans() {
+        let parsed =
+            ParsedHttpRequest::parse("GET /path HTTP/1.1\nx-folded: a\n  b\n\tc\nx-key: 1\n\n")
+                .unwrap();
+
+        assert!(parsed.used_obs_fold());
+        assert_eq!(vec![19..38, 38..47], *parsed.header_spans());
+        assert_eq!(vec![19..38], parsed.folded_header_spans());
+        assert_eq!(vec!["x-folded", "x-key"], parsed.header_keys());
+
+        let request: HttpRequest = parsed.try_into().unwrap();
+        assert_eq!(
+            vec![
+                HttpHeader::new("x-folded", "a b c"),
+                HttpHeader::new("x-key", "1")
+            ],
+            request.headers
+        );
+
+        let parsed = ParsedHttpRequest::parse("GET /path HTTP/1.1\nx-key: 1\n\n").unwrap();
+
+        assert!(!parsed.used_obs_fold());
+        assert!(parsed.folded_header_spans().is_empty());
+    }
+
+    #[test]
+    fn validates_host() {
+        for message in [
+            "GET /path HTTP/1.1\nhost: example.com\n\n",
+            "GET /path HTTP/1.0\n\n",
+            "GET /path\n",
+        ] {
+            let parsed = ParsedHttpRequest::parse(message).unwrap();
+            assert_eq!(Ok(()), parsed.validate_host(), "{message:?}");
+        }
+
+        for message in ["GET /path HTTP/1.1\nx-key: 1\n\n", "GET /path HTTP/2\n\n"] {
+            let parsed = ParsedHttpRequest::parse(message).unwrap();
+            assert_eq!(
+                Err(Error::MissingHost),
+                parsed.validate_host(),
+                "{message:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn verifies_content_length() {
+        let parsed = ParsedHttpRequest::parse(
+            "POST https://example.com HTTP/1.1\ncontent-length: 4\n\nbody",
+        )
+        .unwrap();
+        assert_eq!(Ok(()), parsed.verify_content_length());
+
+        let parsed = ParsedHttpRequest::parse(
+            "POST https://example.com HTTP/1.1\nContent-Length: 10\n\nbody",
+        )
+        .unwrap();
+        assert_eq!(
+            Err(Error::BodyLengthMismatch {
+                declared: 10,
+                actual: 4
+            }),
+            parsed.verify_content_length()
+        );
+
+        let parsed = ParsedHttpRequest::parse(
+            "POST https://example.com HTTP/1.1\nContent-Length: x\n\nbody",
+        )
+        .unwrap();
+        assert_eq!(
+            Err(Error::invalid_header("Content-Length")),
+            parsed.verify_content_length()
+        );
+
+        let parsed = ParsedHttpRequest::parse("POST https://example.com HTTP/1.1\n\nbody").unwrap();
+        assert_eq!(Ok(()), parsed.verify_content_length());
+
+        let parsed =
+            ParsedHttpRequest::parse("GET https://example.com HTTP/1.1\nContent-Length: 4\n\n")
+                .unwrap();
+        assert_eq!(Ok(()), parsed.verify_content_length());
+    }
+
+    #[test]
+    fn returns_query_param_spans() {
+        let message = "GET https://example.com/?a=1&flag&&b=%20#frag HTTP/1.1\n\n";
+        let parsed = ParsedHttpRequest::parse(message).unwrap();
+
+        let params = parsed.query_param_spans();
+
+        assert_eq!(
+            vec![(25..26, 27..28), (29..33, 33..33), (35..36, 37..40)],
+            params
+        );
+        assert_eq!(("b", "%20"), (&message[35..36], &message[37..40]));
+
+        let parsed = ParsedHttpRequest::parse("GET https://example.com/ HTTP/1.1\n\n").unwrap();
+
+        assert!(parsed.query_param_spans().is_empty());
+    }
+
+    #[test]
+    fn returns_all_spans() {
+        let parsed =
+            ParsedHttpRequest::parse("POST https://example.com HTTP/1.1\nx-key: 123\n\n{}")
+                .unwrap();
+
+        assert_eq!(
+            RequestSpans {
+                method: 0..4,
+                uri: 5..24,
+                http_version: 25..33,
+                headers: vec![34..45],
+                body: Some(46..48),
+            },
+            parsed.spans()
+        );
+    }
+
+    #[test]
+    fn implements_default() {
+        let parsed = ParsedHttpRequest::default();
+
+        assert_eq!(
+            ParsedHttpRequest::parsed(
+                "GET https://example.com HTTP/1.1\n\n",
+                0..3,
+                4..23,
+                24..32,
+                vec![],
+                None
+            ),
+            parsed
+        );
+
+        assert_eq!("GET https://example.com HTTP/1.1\n\n", parsed.message());
+
+        let request: HttpRequest = parsed.try_into().unwrap();
 
         assert_eq!(
             HttpRequest {
@@ -388,4 +1747,41 @@ mod tests {
             request
         );
     }
+
+    #[test]
+    fn converts_origin_form_target_to_http_request() {
+        let parsed = ParsedHttpRequest::parse("GET /users?id=1 HTTP/1.1\n\n").unwrap();
+
+        let request: HttpRequest = parsed.try_into().unwrap();
+
+        assert!(request.uri.is_relative());
+        assert_eq!("/users", request.uri.path());
+        assert_eq!(Some("id=1"), request.uri.query());
+        assert_eq!("/users?id=1", request.request_target());
+
+        let parsed = ParsedHttpRequest::parse("GET / HTTP/1.1\n\n").unwrap();
+        let request: Result<HttpRequest, Error> = parsed.try_into();
+
+        assert_eq!("/", request.unwrap().uri.to_string());
+    }
+
+    #[test]
+    fn converts_network_path_like_target_to_path() {
+        let parsed = ParsedHttpRequest::parse("GET //a/b HTTP/1.1\n\n").unwrap();
+
+        let request: HttpRequest = parsed.try_into().unwrap();
+
+        assert_eq!(None, request.uri.host());
+        assert_eq!("//a/b", request.uri.path());
+        assert_eq!("//a/b", request.request_target());
+    }
+
+    #[test]
+    fn converts_header_without_colon_to_error() {
+        let parsed = ParsedHttpRequest::parse("GET https://a HTTP/1.1\ninvalid\n\n").unwrap();
+
+        let request: Result<HttpRequest, Error> = parsed.try_into();
+
+        assert_eq!(Err(Error::invalid_header("invalid")), request);
+    }
 }
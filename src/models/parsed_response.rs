@@ -0,0 +1,466 @@
+use core::fmt;
+use std::ops::Range;
+
+use crate::{
+    error::Error,
+    models::{HttpHeader, HttpResponse, HttpStatusCode},
+    span::{
+        Span, bom_len, first_line_span, get_line_spans, header_key_span, header_value_span,
+        is_blank_line, offset_span,
+    },
+};
+
+/// A spec compliant HTTP response message with spans for each part
+///
+/// ```skip
+/// HTTP/1.1 200 OK
+/// key: value
+///
+/// body
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct ParsedHttpResponse<'http_message> {
+    message: &'http_message str,
+    http_version: Range<usize>,
+    status_code: Range<usize>,
+    reason: Option<Range<usize>>,
+    headers: Vec<Range<usize>>,
+    body: Option<Range<usize>>,
+}
+
+impl<'http_message> fmt::Display for ParsedHttpResponse<'http_message> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl<'http_message> ParsedHttpResponse<'http_message> {
+    pub fn parse(message: &'http_message str) -> Result<Self, Error> {
+        parse_response(message)
+    }
+
+    pub fn parsed(
+        message: &'http_message str,
+        http_version: Range<usize>,
+        status_code: Range<usize>,
+        reason: Option<Range<usize>>,
+        headers: Vec<Range<usize>>,
+        body: Option<Range<usize>>,
+    ) -> Self {
+        let parsed = Self {
+            message,
+            http_version,
+            status_code,
+            reason,
+            headers,
+            body,
+        };
+
+        parsed.verify_spans();
+
+        parsed
+    }
+
+    /// Verify all the spans in the struct are valid
+    ///
+    /// - Aren't out of bounds of the message
+    /// - Parts aren't overlapping or out of order
+    ///
+    /// Parsing only creates valid spans so these are debug assertions.
+    fn verify_spans(&self) {
+        {
+            debug_assert!(self.http_version.start < self.http_version.end);
+            assert_text_span(self.message(), &self.http_version);
+        };
+
+        {
+            debug_assert!(self.status_code.start < self.status_code.end);
+            assert_text_span(self.message(), &self.status_code);
+
+            debug_assert!(
+                self.http_version.end < self.status_code.start,
+                "status code {:?} and http version {:?} spans conflict",
+                self.status_code,
+                self.http_version
+            );
+        };
+
+        self.reason.as_ref().inspect(|span| {
+            debug_assert!(span.start < span.end);
+            assert_text_span(self.message(), span);
+
+            debug_assert!(
+                self.status_code.end < span.start,
+                "reason {span:?} and status code {:?} spans conflict",
+                self.status_code
+            );
+        });
+
+        for span in self.header_spans().iter() {
+            debug_assert!(span.start < span.end);
+            assert_text_span(self.message(), span);
+        }
+
+        self.body.as_ref().inspect(|span| {
+            debug_assert!(span.start < span.end);
+            assert_text_span(self.message(), span);
+        });
+    }
+
+    /// Get the original HTTP response message text
+    pub fn message(&self) -> &str {
+        self.message
+    }
+
+    /// Get the text span of the status line without its line ending
+    pub fn status_line_span(&self) -> Range<usize> {
+        first_line_span(self.message).unwrap_or_default()
+    }
+
+    /// Get the string text of the status line without its line ending
+    pub fn status_line_str(&self) -> &str {
+        self.slice_message(&self.status_line_span())
+    }
+
+    /// Get the text span of the http version
+    pub fn http_version_span(&self) -> &Range<usize> {
+        &self.http_version
+    }
+
+    /// Get the string text of the http version
+    pub fn http_version_str(&self) -> &str {
+        self.slice_message(&self.http_version)
+    }
+
+    /// Get the text span of the status code
+    pub fn status_code_span(&self) -> &Range<usize> {
+        &self.status_code
+    }
+
+    /// Get the string text of the status code
+    pub fn status_code_str(&self) -> &str {
+        self.slice_message(&self.status_code)
+    }
+
+    /// Get the text span of the reason phrase, if defined
+    pub fn reason_span(&self) -> &Option<Range<usize>> {
+        &self.reason
+    }
+
+    /// Get the string text of the reason phrase, if defined
+    pub fn reason_str(&self) -> Option<&str> {
+        self.reason.as_ref().map(|span| self.slice_message(span))
+    }
+
+    /// Get a list of the header line text spans
+    pub fn header_spans(&self) -> &Vec<Range<usize>> {
+        &self.headers
+    }
+
+    /// Get each header key in order, including duplicates
+    pub fn header_keys(&self) -> Vec<&str> {
+        self.headers
+            .iter()
+            .map(|span| self.slice_message(&header_key_span(self.message, span)))
+            .collect()
+    }
+
+    /// Get a list of the string text header lines
+    pub fn header_strs(&self) -> Vec<&str> {
+        self.headers
+            .iter()
+            .map(|span| self.slice_message(span))
+            .collect()
+    }
+
+    /// Get the text span of a header line by key, if defined
+    pub fn header_span(&self, key: &str) -> Option<&Range<usize>> {
+        self.headers
+            .iter()
+            .find(|span| self.slice_message(span).starts_with(&format!("{key}:")))
+    }
+
+    /// Get the string text of a header by key, if defined
+    pub fn header_str(&self, key: &str) -> Option<&str> {
+        self.header_span(key).map(|span| self.slice_message(span))
+    }
+
+    /// Get the text span of a header value by key, if defined
+    pub fn header_value_span(&self, key: &str) -> Option<Range<usize>> {
+        self.header_span(key)
+            .and_then(|span| header_value_span(self.message, span))
+    }
+
+    /// Get the trimmed string text and text span of a header value by key, if defined
+    pub fn header_value(&self, key: &str) -> Option<(&str, Range<usize>)> {
+        self.header_value_span(key)
+            .map(|span| (self.slice_message(&span), span))
+    }
+
+    /// Get the raw string text of the body, if defined
+    ///
+    /// This is exactly the text after the separator line, including any trailing
+    /// line terminators. See [ParsedHttpResponse::body_trimmed] to exclude them.
+    pub fn body_str(&self) -> Option<&str> {
+        self.body.as_ref().map(|span| &self.message[span.clone()])
+    }
+
+    /// Get the string text of the body without trailing `\r` and `\n`, if defined
+    pub fn body_trimmed(&self) -> Option<&str> {
+        self.body_str()
+            .map(|body| body.trim_end_matches(['\r', '\n']))
+    }
+
+    /// Return a slice of the message string
+    fn slice_message(&self, span: &Span) -> &str {
+        &self.message[span.clone()]
+    }
+}
+
+fn assert_text_span(text: &str, span: &Range<usize>) {
+    debug_assert!(
+        text.get(span.clone()).is_some(),
+        "span {span:?} is outside of text bounds"
+    );
+}
+
+impl<'http_message> Default for ParsedHttpResponse<'http_message> {
+    fn default() -> Self {
+        Self::parse("HTTP/1.1 200 OK\n\n").unwrap()
+    }
+}
+
+fn parse_response(input: &str) -> Result<ParsedHttpResponse<'_>, Error> {
+    let bom_len = bom_len(input);
+
+    if input[bom_len..].trim().is_empty() {
+        return Err(Error::EmptyHttpMessage);
+    }
+
+    let line_spans = get_line_spans(input);
+
+    let status_line = first_line_span(input).ok_or(Error::EmptyHttpMessage)?;
+
+    let (http_version, status_code, reason) = parse_status_line(&input[status_line.clone()]);
+
+    let http_version = http_version
+        .map(|span| offset_span(span, status_line.start))
+        .ok_or(Error::missing_required("http_version"))?;
+
+    let status_code = status_code
+        .map(|span| offset_span(span, status_line.start))
+        .ok_or(Error::missing_required("status_code"))?;
+
+    let reason = reason.map(|span| offset_span(span, status_line.start));
+
+    input[status_code.clone()].parse::<HttpStatusCode>()?;
+
+    let first_empty_line_idx = line_spans
+        .iter()
+        .position(|span| is_blank_line(input, span))
+        .ok_or(Error::missing_required("empty line"))?;
+
+    let header_spans = line_spans[1..first_empty_line_idx].to_vec();
+
+    let separator = &line_spans[first_empty_line_idx];
+    let body_span = separator.end..input.len();
+    let body_span = (!body_span.is_empty()).then_some(body_span);
+
+    Ok(ParsedHttpResponse::parsed(
+        input,
+        http_version,
+        status_code,
+        reason,
+        header_spans,
+        body_span,
+    ))
+}
+
+/// Parse the status line of an HTTP response message
+///
+/// The reason phrase is everything after the status code and may contain spaces.
+fn parse_status_line(status_line: &str) -> (Option<Span>, Option<Span>, Option<Span>) {
+    let mut parts = vec![];
+    let mut last_end = 0;
+
+    for (i, c) in status_line.char_indices() {
+        if parts.len() == 2 {
+            break;
+        }
+
+        if c.is_whitespace() {
+            if i > last_end {
+                parts.push(last_end..i);
+            }
+            last_end = i + c.len_utf8();
+        }
+    }
+
+    if parts.len() < 2 && last_end < status_line.len() {
+        parts.push(last_end..status_line.len());
+    }
+
+    let reason = parts.get(1).and_then(|status_code| {
+        let rest = &status_line[status_code.end..];
+        let start = status_code.end + (rest.len() - rest.trim_start().len());
+        let end = status_code.end + rest.trim_end().len();
+
+        (start < end).then_some(start..end)
+    });
+
+    (parts.first().cloned(), parts.get(1).cloned(), reason)
+}
+
+impl<'a> TryFrom<ParsedHttpResponse<'a>> for HttpResponse {
+    type Error = Error;
+
+    fn try_from(value: ParsedHttpResponse<'a>) -> Result<Self, Self::Error> {
+        Ok(HttpResponse::from_parts(
+            value.http_version_str().into(),
+            value.status_code_str().parse()?,
+            value.reason_str().map(|reason| reason.to_string()),
+            value
+                .header_strs()
+                .into_iter()
+                .map(HttpHeader::parse)
+                .collect::<Result<Vec<HttpHeader>, Error>>()?,
+            value.body_str().map(|body| body.to_string()),
+        ))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::reversed_empty_ranges, clippy::single_range_in_vec_init)]
+mod tests {
+    use crate::{
+        error::Error,
+        models::{HttpResponse, HttpStatusCode, ParsedHttpResponse},
+    };
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn verifies_out_of_bounds_http_version_span() {
+        ParsedHttpResponse::parsed("", 1..2, 0..0, None, vec![], None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn verifies_inverted_status_code_span() {
+        ParsedHttpResponse::parsed("HTTP/1.1 200", 0..8, 2..1, None, vec![], None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn verifies_status_code_overlaps_http_version() {
+        ParsedHttpResponse::parsed("HTTP/1.1 200", 0..8, 5..12, None, vec![], None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn verifies_out_of_bounds_reason_span() {
+        ParsedHttpResponse::parsed("HTTP/1.1 200", 0..8, 9..12, Some(13..15), vec![], None);
+    }
+
+    #[test]
+    fn parses_response() {
+        let message = "HTTP/1.1 404 Not Found\nContent-Type: text/plain\n\nmissing";
+
+        let parsed = ParsedHttpResponse::parse(message).unwrap();
+
+        assert_eq!(
+            ParsedHttpResponse::parsed(
+                message,
+                0..8,
+                9..12,
+                Some(13..22),
+                vec![23..48],
+                Some(49..56)
+            ),
+            parsed
+        );
+
+        assert_eq!("HTTP/1.1", parsed.http_version_str());
+        assert_eq!("404", parsed.status_code_str());
+        assert_eq!(Some("Not Found"), parsed.reason_str());
+        assert_eq!(
+            Some(("text/plain", 37..47)),
+            parsed.header_value("Content-Type")
+        );
+        assert_eq!(Some("missing"), parsed.body_str());
+    }
+
+    #[test]
+    fn returns_status_line() {
+        let parsed = ParsedHttpResponse::parse("HTTP/1.1 200 OK\r\nx-key: 123\r\n\r\n").unwrap();
+
+        assert_eq!(0..15, parsed.status_line_span());
+        assert_eq!("HTTP/1.1 200 OK", parsed.status_line_str());
+    }
+
+    #[test]
+    fn parses_response_without_reason() {
+        let parsed = ParsedHttpResponse::parse("HTTP/1.1 204 \n\n").unwrap();
+
+        assert_eq!(&None, parsed.reason_span());
+        assert_eq!("HTTP/1.1 204", parsed.status_line_str().trim_end());
+    }
+
+    #[test]
+    fn parses_to_errors() {
+        assert_eq!(
+            Err(Error::EmptyHttpMessage),
+            ParsedHttpResponse::parse(" \n")
+        );
+        assert_eq!(
+            Err(Error::missing_required("status_code")),
+            ParsedHttpResponse::parse("HTTP/1.1\n\n")
+        );
+        assert_eq!(
+            Err(Error::invalid_status_code("abc")),
+            ParsedHttpResponse::parse("HTTP/1.1 abc\n\n")
+        );
+        assert_eq!(
+            Err(Error::invalid_status_code("+200")),
+            ParsedHttpResponse::parse("HTTP/1.1 +200 OK\n\n")
+        );
+        assert_eq!(
+            Err(Error::missing_required("empty line")),
+            ParsedHttpResponse::parse("HTTP/1.1 200 OK")
+        );
+    }
+
+    #[test]
+    fn converts_header_without_colon_to_error() {
+        let parsed = ParsedHttpResponse::parse("HTTP/1.1 200 OK\nbad\n\n").unwrap();
+
+        let response: Result<HttpResponse, Error> = parsed.try_into();
+
+        assert_eq!(Some(Error::invalid_header("bad")), response.err());
+    }
+
+    #[test]
+    fn converts_to_http_response() {
+        let parsed =
+            ParsedHttpResponse::parse("HTTP/1.0 201 Created\nx-key: 123\n\n{\"id\": 1}").unwrap();
+
+        let response: HttpResponse = parsed.try_into().unwrap();
+
+        assert_eq!(HttpStatusCode::new(201), response.status_code);
+        assert_eq!(Some("Created".to_string()), response.reason);
+        assert_eq!("HTTP/1.0", response.http_version.to_string());
+        assert_eq!("123", response.get_header("x-key").unwrap().value());
+        assert_eq!(Some("{\"id\": 1}".to_string()), response.body);
+    }
+
+    #[test]
+    fn implements_default() {
+        let parsed = ParsedHttpResponse::default();
+
+        assert_eq!("HTTP/1.1 200 OK\n\n", parsed.message());
+        assert_eq!("200", parsed.status_code_str());
+    }
+}
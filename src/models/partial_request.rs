@@ -1,9 +1,15 @@
 use core::fmt;
-use std::ops::Range;
+use std::{collections::HashMap, ops::Range};
 
 use crate::{
     error::Error,
-    span::{Span, get_line_spans},
+    escape::escape_control_chars,
+    models::{Anomaly, AnomalyKind},
+    span::{
+        PartialRequestSpans, Span, bom_len, get_line_spans, header_key_span_with_separator,
+        header_value_span_with_separator, is_blank_line, offset_span, trim_line_ending,
+    },
+    template::find_template_vars,
 };
 
 /// A partial HTTP request that might not conform to HTTP spec
@@ -17,8 +23,33 @@ pub struct PartialHttpRequest<'http_message> {
     http_version: Option<Range<usize>>,
     headers: Vec<Range<usize>>,
     body: Option<Range<usize>>,
+    comments: Vec<Range<usize>>,
+    header_separator: char,
 }
 
+/// How the request line tokens were assigned to the method, uri, and http version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FirstLineShape {
+    /// There is no request line
+    Empty,
+    /// A lone token that doesn't look like a uri, e.g. `GET`
+    MethodOnly,
+    /// A lone uri-like token, e.g. `/path` or `https://example.com`
+    UriOnly,
+    /// A method and uri without a version, e.g. `GET /path`
+    MethodUri,
+    /// A method and version-like second token without a uri, e.g. `GET HTTP/1.1`
+    MethodVersion,
+    /// A method, uri, and version
+    Full,
+}
+
+/// Line prefixes treated as comments by [PartialHttpRequest::parse]
+///
+/// Before the request line a lone uri-like token such as `//example.com/x` is the
+/// request line's uri rather than a comment.
+pub const DEFAULT_COMMENT_PREFIXES: [&str; 2] = ["#", "//"];
+
 impl<'http_message> fmt::Display for PartialHttpRequest<'http_message> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.message())
@@ -26,8 +57,43 @@ impl<'http_message> fmt::Display for PartialHttpRequest<'http_message> {
 }
 
 impl<'http_message> PartialHttpRequest<'http_message> {
+    /// Parse a message, skipping `#` and `//` comment lines before the body
     pub fn parse(message: &'http_message str) -> Result<Self, Error> {
-        parse_request(message, parse_first_line)
+        parse_request(message, parse_first_line, &DEFAULT_COMMENT_PREFIXES)
+    }
+
+    /// Parse a message, skipping lines starting with any of the prefixes before the body
+    ///
+    /// Comment lines are excluded from the request line and headers but their spans
+    /// are available from [PartialHttpRequest::comment_spans].
+    pub fn parse_with_comment_prefixes(
+        message: &'http_message str,
+        comment_prefixes: &[&str],
+    ) -> Result<Self, Error> {
+        parse_request(message, parse_first_line, comment_prefixes)
+    }
+
+    /// Parse a message like [PartialHttpRequest::parse] but allow unencoded spaces in the uri
+    ///
+    /// A request line like `GET /search?q=hello world HTTP/1.1` has the uri
+    /// `/search?q=hello world` instead of treating `world` as the version. See
+    /// [parse_first_line_lenient].
+    pub fn parse_with_lenient_uri(message: &'http_message str) -> Result<Self, Error> {
+        parse_request(message, parse_first_line_lenient, &DEFAULT_COMMENT_PREFIXES)
+    }
+
+    /// Parse a message like [PartialHttpRequest::parse] with a custom header key/value separator
+    ///
+    /// Header lookups, anomalies, and conversion to [HttpRequest] use the separator
+    /// instead of `:`, e.g. `=` or a tab for near-HTTP formats.
+    pub fn parse_with_header_separator(
+        message: &'http_message str,
+        separator: char,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            header_separator: separator,
+            ..parse_request(message, parse_first_line, &DEFAULT_COMMENT_PREFIXES)?
+        })
     }
 
     pub fn parsed(
@@ -45,6 +111,8 @@ impl<'http_message> PartialHttpRequest<'http_message> {
             http_version,
             headers,
             body,
+            comments: vec![],
+            header_separator: ':',
         };
 
         partial.verify_spans();
@@ -56,41 +124,45 @@ impl<'http_message> PartialHttpRequest<'http_message> {
     ///
     /// - Aren't out of bounds of the message
     /// - Parts aren't overlapping or out of order
+    ///
+    /// Parsing only creates valid spans so these are debug assertions.
     fn verify_spans(&self) {
         self.method.as_ref().inspect(|span| {
-            assert!(span.start < span.end);
+            debug_assert!(span.start < span.end);
             assert_text_span(self.message(), span);
         });
 
         self.uri.as_ref().inspect(|span| {
-            assert!(span.start < span.end);
+            debug_assert!(span.start < span.end);
             assert_text_span(self.message(), span);
 
             if let Some(method) = self.method_span() {
-                if !(method.start < span.start && method.end < span.start) {
-                    panic!("uri {span:?} and method {method:?} spans conflict");
-                }
+                debug_assert!(
+                    method.start < span.start && method.end < span.start,
+                    "uri {span:?} and method {method:?} spans conflict"
+                );
             }
         });
 
         self.http_version.as_ref().inspect(|span| {
-            assert!(span.start < span.end);
+            debug_assert!(span.start < span.end);
             assert_text_span(self.message(), span);
 
             if let Some(uri) = self.uri_span() {
-                if !(uri.start < span.start && uri.end < span.start) {
-                    panic!("http version {span:?} and uri {uri:?} spans conflict");
-                }
+                debug_assert!(
+                    uri.start < span.start && uri.end < span.start,
+                    "http version {span:?} and uri {uri:?} spans conflict"
+                );
             }
         });
 
         for span in self.header_spans().iter() {
-            assert!(span.start < span.end);
+            debug_assert!(span.start < span.end);
             assert_text_span(self.message(), span);
         }
 
         self.body.as_ref().inspect(|span| {
-            assert!(span.start < span.end);
+            debug_assert!(span.start < span.end);
             assert_text_span(self.message(), span);
         });
     }
@@ -110,6 +182,21 @@ impl<'http_message> PartialHttpRequest<'http_message> {
         self.uri.as_ref().map(|span| self.slice_message(span))
     }
 
+    /// Get how the request line tokens were assigned
+    ///
+    /// This makes the first line heuristics inspectable, e.g. to warn that a lone
+    /// `GET` was treated as a method.
+    pub fn first_line_classification(&self) -> FirstLineShape {
+        match (&self.method, &self.uri, &self.http_version) {
+            (Some(_), Some(_), Some(_)) => FirstLineShape::Full,
+            (Some(_), None, Some(_)) => FirstLineShape::MethodVersion,
+            (Some(_), Some(_), None) => FirstLineShape::MethodUri,
+            (Some(_), None, None) => FirstLineShape::MethodOnly,
+            (None, Some(_), _) => FirstLineShape::UriOnly,
+            (None, None, _) => FirstLineShape::Empty,
+        }
+    }
+
     /// Get the text span of the method, if defined
     pub fn method_span(&self) -> &Option<Range<usize>> {
         &self.method
@@ -137,6 +224,25 @@ impl<'http_message> PartialHttpRequest<'http_message> {
         &self.headers
     }
 
+    /// Get each header key in order, including duplicates
+    pub fn header_keys(&self) -> Vec<&str> {
+        self.headers
+            .iter()
+            .map(|span| {
+                self.slice_message(&header_key_span_with_separator(
+                    self.message,
+                    span,
+                    self.header_separator,
+                ))
+            })
+            .collect()
+    }
+
+    /// Get the separator between header keys and values, `:` by default
+    pub fn header_separator(&self) -> char {
+        self.header_separator
+    }
+
     /// Get a list of the string text header lines
     pub fn header_strs(&self) -> Vec<&str> {
         self.headers
@@ -147,9 +253,11 @@ impl<'http_message> PartialHttpRequest<'http_message> {
 
     /// Get the text span of a header line by key, if defined
     pub fn header_span(&self, key: &str) -> Option<&Range<usize>> {
-        self.headers
-            .iter()
-            .find(|span| self.slice_message(span).starts_with(&format!("{key}:")))
+        self.headers.iter().find(|span| {
+            self.slice_message(span)
+                .strip_prefix(key)
+                .is_some_and(|rest| rest.starts_with(self.header_separator))
+        })
     }
 
     /// Get the string text of a header by key, if defined
@@ -157,11 +265,146 @@ impl<'http_message> PartialHttpRequest<'http_message> {
         self.header_span(key).map(|span| self.slice_message(span))
     }
 
-    /// Get the string text of the body, if defined
+    /// Get the text span of a header value by key, if defined
+    ///
+    /// The span excludes surrounding whitespace and the line ending. An empty
+    /// value has an empty span positioned after the colon and any whitespace.
+    pub fn header_value_span(&self, key: &str) -> Option<Range<usize>> {
+        self.header_span(key).and_then(|span| {
+            header_value_span_with_separator(self.message, span, self.header_separator)
+        })
+    }
+
+    /// Get the trimmed string text and text span of a header value by key, if defined
+    pub fn header_value(&self, key: &str) -> Option<(&str, Range<usize>)> {
+        self.header_value_span(key)
+            .map(|span| (self.slice_message(&span), span))
+    }
+
+    /// Get a list of the comment line text spans
+    pub fn comment_spans(&self) -> &Vec<Range<usize>> {
+        &self.comments
+    }
+
+    /// Get the raw string text of the body, if defined
+    ///
+    /// This is exactly the text after the separator line, including any trailing
+    /// line terminators. See [PartialHttpRequest::body_trimmed] to exclude them.
     pub fn body_str(&self) -> Option<&str> {
         self.body.as_ref().map(|span| &self.message[span.clone()])
     }
 
+    /// Get the string text of the body without trailing `\r` and `\n`, if defined
+    pub fn body_trimmed(&self) -> Option<&str> {
+        self.body_str()
+            .map(|body| body.trim_end_matches(['\r', '\n']))
+    }
+
+    /// Get all the text spans
+    pub fn spans(&self) -> PartialRequestSpans {
+        PartialRequestSpans {
+            method: self.method.clone(),
+            uri: self.uri.clone(),
+            http_version: self.http_version.clone(),
+            headers: self.headers.clone(),
+            body: self.body.clone(),
+        }
+    }
+
+    /// Get the non-fatal irregularities in the message, ordered by position
+    ///
+    /// These are allowed in partial requests but are useful as soft warnings.
+    pub fn anomalies(&self) -> Vec<Anomaly> {
+        let mut anomalies = vec![];
+
+        let parts: Vec<&Range<usize>> = [&self.method, &self.uri, &self.http_version]
+            .into_iter()
+            .flatten()
+            .collect();
+
+        for pair in parts.windows(2) {
+            let gap = pair[0].end..pair[1].start;
+
+            if gap.len() > 1 {
+                anomalies.push(Anomaly::new(gap, AnomalyKind::MultipleSpaces));
+            }
+        }
+
+        if let (Some(uri), None) = (&self.uri, &self.http_version) {
+            anomalies.push(Anomaly::new(uri.end..uri.end, AnomalyKind::MissingVersion));
+        }
+
+        let request_line = parts.first().and_then(|part| {
+            get_line_spans(self.message)
+                .into_iter()
+                .find(|line| line.contains(&part.start))
+        });
+
+        for line in request_line.iter().chain(self.headers.iter()) {
+            let content = trim_line_ending(self.message, line);
+            let text = self.slice_message(&content);
+            let trimmed_end = content.start + text.trim_end_matches([' ', '\t']).len();
+
+            if trimmed_end < content.end {
+                anomalies.push(Anomaly::new(
+                    trimmed_end..content.end,
+                    AnomalyKind::TrailingWhitespace,
+                ));
+            }
+        }
+
+        for header in self.headers.iter() {
+            let content = trim_line_ending(self.message, header);
+
+            if !self.slice_message(&content).contains(self.header_separator) {
+                anomalies.push(Anomaly::new(content, AnomalyKind::HeaderWithoutColon));
+            }
+        }
+
+        anomalies.sort_by_key(|anomaly| anomaly.span.start);
+
+        anomalies
+    }
+
+    /// Get the message with control characters escaped for safe terminal printing
+    ///
+    /// Escape sequences in an untrusted body can't be interpreted by the terminal.
+    pub fn to_display_safe(&self) -> String {
+        escape_control_chars(self.message)
+    }
+
+    /// Get each `{{name}}` template variable name and the span of its placeholder
+    ///
+    /// Every occurrence is returned in order, including repeats of the same name.
+    pub fn template_var_spans(&self) -> Vec<(String, Range<usize>)> {
+        find_template_vars(self.message)
+    }
+
+    /// Substitute `{{name}}` template variables in the message with values from vars
+    ///
+    /// The result can be parsed again, e.g. strictly with [crate::parse_request].
+    pub fn expand(&self, vars: &HashMap<String, String>) -> Result<String, Error> {
+        let mut expanded = String::with_capacity(self.message.len());
+        let mut last_end = 0;
+
+        for (name, span) in find_template_vars(self.message) {
+            let value = vars
+                .get(&name)
+                .ok_or_else(|| Error::UnresolvedTemplateVariable {
+                    name: name.clone(),
+                    span: span.clone(),
+                })?;
+
+            expanded.push_str(&self.message[last_end..span.start]);
+            expanded.push_str(value);
+            last_end = span.end;
+        }
+
+        expanded.push_str(&self.message[last_end..]);
+
+        Ok(expanded)
+    }
+
     /// Return a slice of the message string
     fn slice_message(&self, span: &Span) -> &str {
         &self.message[span.clone()]
@@ -169,8 +412,10 @@ impl<'http_message> PartialHttpRequest<'http_message> {
 }
 
 fn assert_text_span(text: &str, span: &Range<usize>) {
-    text.get(span.clone())
-        .unwrap_or_else(|| panic!("span {span:?} is outside of text bounds"));
+    debug_assert!(
+        text.get(span.clone()).is_some(),
+        "span {span:?} is outside of text bounds"
+    );
 }
 
 impl<'http_message> Default for PartialHttpRequest<'http_message> {
@@ -179,7 +424,8 @@ impl<'http_message> Default for PartialHttpRequest<'http_message> {
     }
 }
 
-type FirstLineParts = (
+/// The method, uri, and http version spans of a request line, if found
+pub type FirstLineParts = (
     Option<Range<usize>>,
     Option<Range<usize>>,
     Option<Range<usize>>,
@@ -188,11 +434,14 @@ type FirstLineParts = (
 fn parse_request<'http_message, F>(
     input: &'http_message str,
     parse_first_line: F,
+    comment_prefixes: &[&str],
 ) -> Result<PartialHttpRequest<'http_message>, Error>
 where
     F: Fn(&str) -> FirstLineParts,
 {
-    if input.trim().is_empty() {
+    let bom_len = bom_len(input);
+
+    if input[bom_len..].trim().is_empty() {
         return Ok(PartialHttpRequest::parsed(
             input,
             None,
@@ -205,31 +454,128 @@ where
 
     let line_spans = get_line_spans(input);
 
-    let first_empty_line_idx = line_spans.iter().position(|span| span.len() == 1);
+    let first_empty_line_idx = line_spans
+        .iter()
+        .position(|span| is_blank_line(input, span));
+
+    let head_end = first_empty_line_idx.unwrap_or(line_spans.len());
+
+    let mut found_request_line = false;
 
-    let first_line = line_spans.first();
+    let (comment_spans, head_spans): (Vec<Range<usize>>, Vec<Range<usize>>) =
+        line_spans[..head_end].iter().cloned().partition(|span| {
+            let is_comment = is_comment_line(input, span, bom_len, comment_prefixes)
+                && (found_request_line || !is_lone_uri_line(input, span, bom_len));
 
-    let (method, uri, http_version) = first_line
-        .map(|span| &input[span.clone()])
-        .map(parse_first_line)
+            found_request_line |= !is_comment;
+
+            is_comment
+        });
+
+    let mut head_spans = head_spans.into_iter();
+
+    let (method, uri, http_version) = head_spans
+        .next()
+        .map(|span| {
+            let start = span.start.max(bom_len);
+            let (method, uri, http_version) = parse_first_line(&input[start..span.end]);
+
+            (
+                method.map(|span| offset_span(span, start)),
+                uri.map(|span| offset_span(span, start)),
+                http_version.map(|span| offset_span(span, start)),
+            )
+        })
         .unwrap_or((None, None, None));
 
-    let (header_spans, body_spans) = get_header_and_body_spans(line_spans, first_empty_line_idx);
+    let header_spans: Vec<Range<usize>> = head_spans.collect();
+
+    let body_spans = first_empty_line_idx.map(|idx| line_spans[idx..].to_vec());
 
     let body_span = get_span_extent_from_spans(body_spans);
 
-    Ok(PartialHttpRequest::parsed(
-        input,
-        method,
-        uri,
-        http_version,
-        header_spans,
-        body_span,
-    ))
+    Ok(PartialHttpRequest {
+        comments: comment_spans,
+        ..PartialHttpRequest::parsed(input, method, uri, http_version, header_spans, body_span)
+    })
+}
+
+/// Check if a line starts with any of the comment prefixes, ignoring a leading BOM
+fn is_comment_line(input: &str, line: &Span, bom_len: usize, comment_prefixes: &[&str]) -> bool {
+    let line = &input[line.start.max(bom_len)..line.end];
+
+    comment_prefixes
+        .iter()
+        .any(|prefix| !prefix.is_empty() && line.starts_with(prefix))
+}
+
+/// Check if a line is a single uri-like token, ignoring a leading BOM
+fn is_lone_uri_line(input: &str, line: &Span, bom_len: usize) -> bool {
+    let line = &input[line.start.max(bom_len)..line.end];
+
+    match split_first_line(line).as_slice() {
+        [token] => looks_like_uri(&line[token.clone()]),
+        _ => false,
+    }
 }
 
 /// Parse the first line of an HTTP request message
+///
+/// A lone uri-like token is the uri and a version-like second token is the version.
 fn parse_first_line(first_line: &str) -> FirstLineParts {
+    let parts = split_first_line(first_line);
+
+    match parts.as_slice() {
+        [span] if looks_like_uri(&first_line[span.clone()]) => {
+            return (None, Some(span.clone()), None);
+        }
+        [method, http_version] if looks_like_http_version(&first_line[http_version.clone()]) => {
+            return (Some(method.clone()), None, Some(http_version.clone()));
+        }
+        _ => {}
+    }
+
+    let method_span = parts.first().cloned();
+    let uri_span = parts.get(1).cloned();
+    let http_version_span = parts.get(2).cloned();
+
+    (method_span, uri_span, http_version_span)
+}
+
+/// Split a request line in to method, uri, and version spans, allowing spaces in the uri
+///
+/// When there are more than three tokens and the last looks like a version, the
+/// tokens between the method and version are joined as the uri.
+///
+/// ```
+/// use http_message::models::parse_first_line_lenient;
+///
+/// let line = "GET /search?q=hello world HTTP/1.1";
+/// let (method, uri, http_version) = parse_first_line_lenient(line);
+///
+/// assert_eq!(&line[method.unwrap()], "GET");
+/// assert_eq!(&line[uri.unwrap()], "/search?q=hello world");
+/// assert_eq!(&line[http_version.unwrap()], "HTTP/1.1");
+/// ```
+pub fn parse_first_line_lenient(first_line: &str) -> FirstLineParts {
+    let parts = split_first_line(first_line);
+
+    match parts.as_slice() {
+        [method, uri_start, .., uri_end, http_version]
+            if looks_like_http_version(&first_line[http_version.clone()]) =>
+        {
+            (
+                Some(method.clone()),
+                Some(uri_start.start..uri_end.end),
+                Some(http_version.clone()),
+            )
+        }
+        _ => parse_first_line(first_line),
+    }
+}
+
+/// Get the spans of the whitespace separated request line tokens
+fn split_first_line(first_line: &str) -> Vec<Range<usize>> {
     let mut parts = vec![];
     let mut last_end = 0;
 
@@ -238,7 +584,7 @@ fn parse_first_line(first_line: &str) -> FirstLineParts {
             if i > last_end {
                 parts.push(last_end..i);
             }
-            last_end = i + 1;
+            last_end = i + c.len_utf8();
         }
     }
 
@@ -246,79 +592,79 @@ fn parse_first_line(first_line: &str) -> FirstLineParts {
         parts.push(last_end..first_line.len());
     }
 
-    let method_span = parts.first().cloned();
-    let uri_span = parts.get(1).cloned();
-    let http_version_span = parts.get(2).cloned();
-
-    (method_span, uri_span, http_version_span)
+    parts
 }
 
-fn get_header_and_body_spans(
-    line_spans: Vec<Range<usize>>,
-    first_empty_line_idx: Option<usize>,
-) -> (Vec<Range<usize>>, Option<Vec<Range<usize>>>) {
-    let (header_spans, body_spans) = match first_empty_line_idx {
-        Some(idx) => {
-            let header_spans = line_spans.clone()[1..idx].to_vec();
-            let body_spans = Some(line_spans.clone()[idx..].to_vec());
-
-            (header_spans, body_spans)
-        }
-        None => {
-            let header_spans = line_spans.clone()[1..].to_vec();
-            let body_spans = None;
+/// Check if a request line token looks like `HTTP/x.y`
+fn looks_like_http_version(token: &str) -> bool {
+    token.strip_prefix("HTTP/").is_some_and(|version| {
+        !version.is_empty() && version.chars().all(|c| c.is_ascii_digit() || c == '.')
+    })
+}
 
-            (header_spans, body_spans)
-        }
-    };
-    (header_spans, body_spans)
+/// Check if a lone request line token should be treated as a uri instead of a method
+pub(crate) fn looks_like_uri(token: &str) -> bool {
+    token.contains("://") || token.starts_with('/')
 }
 
+/// Get the body span from the separator line through the last line
+///
+/// The body starts after the full separator line so `\r\n` separators are excluded.
+/// A separator with nothing after it has no body.
 fn get_span_extent_from_spans(body_spans: Option<Vec<Range<usize>>>) -> Option<Range<usize>> {
     body_spans.and_then(|spans| {
-        if spans.is_empty() {
-            return None;
-        }
+        let (separator, last) = (spans.first()?, spans.last()?);
 
-        let first = spans.first().unwrap();
-        let last = spans.last().unwrap();
+        let body_span = separator.end..last.end;
 
-        Some(first.start + 1..last.end)
+        (!body_span.is_empty()).then_some(body_span)
     })
 }
 
 #[cfg(test)]
+#[allow(clippy::reversed_empty_ranges, clippy::single_range_in_vec_init)]
 mod tests {
+    use std::collections::HashMap;
+
     use crate::{
         error::Error,
-        models::{HttpRequest, PartialHttpRequest},
+        models::{
+            Anomaly, AnomalyKind, FirstLineShape, HttpHeader, HttpRequest, PartialHttpRequest,
+            parse_first_line_lenient,
+        },
+        span::PartialRequestSpans,
     };
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_out_of_bounds_method_span() {
         PartialHttpRequest::parsed("", Some(1..2), None, None, vec![], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_inverted_method_span() {
         PartialHttpRequest::parsed("", Some(2..1), None, None, vec![], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_out_of_bounds_uri_span() {
         PartialHttpRequest::parsed("", None, Some(1..2), None, vec![], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_inverted_uri_span() {
         PartialHttpRequest::parsed("", None, Some(2..1), None, vec![], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_method_span_overlaps_uri_span() {
         PartialHttpRequest::parsed(
@@ -332,41 +678,349 @@ mod tests {
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_out_of_bounds_http_version_span() {
         PartialHttpRequest::parsed("", None, None, Some(1..2), vec![], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_inverted_http_version_span() {
         PartialHttpRequest::parsed("", None, None, Some(2..1), vec![], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_out_of_bounds_header_span() {
         PartialHttpRequest::parsed("", None, None, None, vec![1..2], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_inverted_header_span() {
         PartialHttpRequest::parsed("", None, None, None, vec![2..1], None);
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_out_of_bounds_body_span() {
         PartialHttpRequest::parsed("", None, None, None, vec![], Some(1..2));
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic]
     fn verifies_inverted_body_span() {
         PartialHttpRequest::parsed("", None, None, None, vec![], Some(2..1));
     }
 
+    #[test]
+    fn parses_lone_uri_as_uri() {
+        let partial = PartialHttpRequest::parse("https://example.com").unwrap();
+
+        assert_eq!(None, partial.method_str());
+        assert_eq!(Some("https://example.com"), partial.uri_str());
+        assert_eq!(None, partial.http_version_str());
+    }
+
+    #[test]
+    fn parses_lone_path_as_uri() {
+        let partial = PartialHttpRequest::parse("/users?id=1").unwrap();
+
+        assert_eq!(None, partial.method_str());
+        assert_eq!(Some("/users?id=1"), partial.uri_str());
+    }
+
+    #[test]
+    fn parses_method_and_version_without_uri() {
+        let partial = PartialHttpRequest::parse("GET HTTP/1.1\nx-key: 123").unwrap();
+
+        assert_eq!(Some("GET"), partial.method_str());
+        assert_eq!(None, partial.uri_str());
+        assert_eq!(Some("HTTP/1.1"), partial.http_version_str());
+
+        let partial = PartialHttpRequest::parse("GET HTTP").unwrap();
+
+        assert_eq!(Some("HTTP"), partial.uri_str());
+    }
+
+    #[test]
+    fn returns_first_line_classification() {
+        let cases = [
+            ("", FirstLineShape::Empty),
+            ("GET", FirstLineShape::MethodOnly),
+            ("/path", FirstLineShape::UriOnly),
+            ("GET /path", FirstLineShape::MethodUri),
+            ("GET HTTP/1.1", FirstLineShape::MethodVersion),
+            ("GET /path HTTP/1.1", FirstLineShape::Full),
+        ];
+
+        for (message, shape) in cases {
+            let partial = PartialHttpRequest::parse(message).unwrap();
+
+            assert_eq!(shape, partial.first_line_classification(), "{message:?}");
+        }
+    }
+
+    #[test]
+    fn parses_lone_token_as_method() {
+        let partial = PartialHttpRequest::parse("GET").unwrap();
+
+        assert_eq!(Some("GET"), partial.method_str());
+        assert_eq!(None, partial.uri_str());
+    }
+
+    #[test]
+    fn parses_uri_with_spaces_leniently() {
+        let message = "GET /search?q=hello world HTTP/1.1\nx-key: 123\n\n";
+
+        let partial = PartialHttpRequest::parse_with_lenient_uri(message).unwrap();
+
+        assert_eq!(Some("GET"), partial.method_str());
+        assert_eq!(Some("/search?q=hello world"), partial.uri_str());
+        assert_eq!(Some("HTTP/1.1"), partial.http_version_str());
+
+        let partial = PartialHttpRequest::parse(message).unwrap();
+
+        assert_eq!(Some("world"), partial.http_version_str());
+
+        let partial = PartialHttpRequest::parse_with_lenient_uri("GET /a b c").unwrap();
+
+        assert_eq!(Some("/a"), partial.uri_str());
+        assert_eq!(Some("b"), partial.http_version_str());
+    }
+
+    #[test]
+    fn parses_first_line_leniently() {
+        assert_eq!(
+            (Some(0..3), Some(4..25), Some(26..34)),
+            parse_first_line_lenient("GET /search?q=hello world HTTP/1.1")
+        );
+        assert_eq!(
+            (Some(0..3), Some(4..6), Some(7..8)),
+            parse_first_line_lenient("GET /a b c")
+        );
+    }
+
+    #[test]
+    fn skips_leading_bom() {
+        let partial = PartialHttpRequest::parse("\u{FEFF}GET https://example.com").unwrap();
+
+        assert_eq!(&Some(3..6), partial.method_span());
+        assert_eq!(Some("GET"), partial.method_str());
+        assert_eq!(Some("https://example.com"), partial.uri_str());
+    }
+
+    #[test]
+    fn parses_lone_bom_as_empty() {
+        let partial = PartialHttpRequest::parse("\u{FEFF}").unwrap();
+
+        assert_eq!(None, partial.method_str());
+    }
+
+    #[test]
+    fn returns_header_value_and_span() {
+        let partial = PartialHttpRequest::parse("GET https://example.com\nx-key: 123").unwrap();
+
+        assert_eq!(Some(("123", 31..34)), partial.header_value("x-key"));
+        assert_eq!(Some(31..34), partial.header_value_span("x-key"));
+        assert_eq!(None, partial.header_value("x-missing"));
+    }
+
+    #[test]
+    fn parses_with_header_separator() {
+        let message = "GET https://example.com HTTP/1.1\nx-key= 123\nx-other: 1\n\n";
+
+        let partial = PartialHttpRequest::parse_with_header_separator(message, '=').unwrap();
+
+        assert_eq!('=', partial.header_separator());
+        assert_eq!(vec!["x-key", "x-other: 1"], partial.header_keys());
+        assert_eq!(Some(("123", 40..43)), partial.header_value("x-key"));
+        assert_eq!(
+            vec![Anomaly::new(44..54, AnomalyKind::HeaderWithoutColon)],
+            partial.anomalies()
+        );
+
+        let request: Result<HttpRequest, Error> = partial.try_into();
+
+        assert_eq!(Err(Error::invalid_header("x-other: 1")), request);
+
+        let message = "GET https://example.com HTTP/1.1\nx-key\t123\n\n";
+        let partial = PartialHttpRequest::parse_with_header_separator(message, '\t').unwrap();
+        let request: HttpRequest = partial.try_into().unwrap();
+
+        assert_eq!(request.headers, vec![HttpHeader::new("x-key", "123")]);
+    }
+
+    #[test]
+    fn returns_raw_and_trimmed_body() {
+        let partial = PartialHttpRequest::parse("GET https://example.com\n\nbody\n").unwrap();
+
+        assert_eq!(Some("body\n"), partial.body_str());
+        assert_eq!(Some("body"), partial.body_trimmed());
+    }
+
+    #[test]
+    fn parses_trailing_separator_without_body() {
+        let partial = PartialHttpRequest::parse("GET https://example.com HTTP/1.1\n\n").unwrap();
+
+        assert_eq!(None, partial.body_str());
+    }
+
+    #[test]
+    fn returns_no_anomalies_for_well_formed_request() {
+        let partial =
+            PartialHttpRequest::parse("GET https://example.com HTTP/1.1\nx-key: 123\n").unwrap();
+
+        assert_eq!(Vec::<Anomaly>::new(), partial.anomalies());
+    }
+
+    #[test]
+    fn returns_anomalies() {
+        let partial =
+            PartialHttpRequest::parse("GET  https://example.com \nx-key: 123\t\nx-invalid\n")
+                .unwrap();
+
+        assert_eq!(
+            vec![
+                Anomaly::new(3..5, AnomalyKind::MultipleSpaces),
+                Anomaly::new(24..24, AnomalyKind::MissingVersion),
+                Anomaly::new(24..25, AnomalyKind::TrailingWhitespace),
+                Anomaly::new(36..37, AnomalyKind::TrailingWhitespace),
+                Anomaly::new(38..47, AnomalyKind::HeaderWithoutColon),
+            ],
+            partial.anomalies()
+        );
+    }
+
+    #[test]
+    fn returns_display_safe_message() {
+        let partial =
+            PartialHttpRequest::parse("POST https://example.com\r\n\r\n\u{1b}[2J\u{7}").unwrap();
+
+        assert_eq!(
+            "POST https://example.com\r\n\r\n\\x1b[2J\\x07",
+            partial.to_display_safe()
+        );
+    }
+
+    #[test]
+    fn returns_template_var_spans() {
+        let partial =
+            PartialHttpRequest::parse("GET {{host}}/{{id}} HTTP/1.1\nx-host: {{host}}\n\n{{")
+                .unwrap();
+
+        assert_eq!(
+            vec![
+                ("host".to_string(), 4..12),
+                ("id".to_string(), 13..19),
+                ("host".to_string(), 37..45),
+            ],
+            partial.template_var_spans()
+        );
+    }
+
+    #[test]
+    fn expands_template_variables() {
+        let partial =
+            PartialHttpRequest::parse("GET {{base_url}}/users HTTP/1.1\nx-key: {{key}}").unwrap();
+
+        let vars = HashMap::from([
+            ("base_url".to_string(), "https://example.com".to_string()),
+            ("key".to_string(), "abc123".to_string()),
+        ]);
+
+        assert_eq!(
+            Ok("GET https://example.com/users HTTP/1.1\nx-key: abc123".to_string()),
+            partial.expand(&vars)
+        );
+    }
+
+    #[test]
+    fn expands_to_error_for_unresolved_template_variables() {
+        let partial = PartialHttpRequest::parse("GET {{base_url}}/users HTTP/1.1").unwrap();
+
+        assert_eq!(
+            Err(Error::UnresolvedTemplateVariable {
+                name: "base_url".to_string(),
+                span: 4..16
+            }),
+            partial.expand(&HashMap::new())
+        );
+    }
+
+    #[test]
+    fn skips_comment_lines() {
+        let partial = PartialHttpRequest::parse(
+            "# get example\nGET https://example.com HTTP/1.1\n// key\nx-key: 123\n\n# body",
+        )
+        .unwrap();
+
+        assert_eq!(Some("GET"), partial.method_str());
+        assert_eq!(&Some(14..17), partial.method_span());
+        assert_eq!(vec!["x-key: 123\n"], partial.header_strs());
+        assert_eq!(&vec![0..14, 47..54], partial.comment_spans());
+        assert_eq!(Some("# body"), partial.body_str());
+    }
+
+    #[test]
+    fn parses_lone_uri_before_comment_prefix() {
+        let partial = PartialHttpRequest::parse(
+            "//example.com/x
+",
+        )
+        .unwrap();
+
+        assert_eq!(None, partial.method_str());
+        assert_eq!(Some("//example.com/x"), partial.uri_str());
+        assert!(partial.comment_spans().is_empty());
+
+        let partial = PartialHttpRequest::parse(
+            "// get example
+//example.com/x
+//a/b
+x-key: 1",
+        )
+        .unwrap();
+
+        assert_eq!(Some("//example.com/x"), partial.uri_str());
+        assert_eq!(vec!["x-key: 1"], partial.header_strs());
+        assert_eq!(&vec![0..15, 31..37], partial.comment_spans());
+    }
+
+    #[test]
+    fn skips_custom_comment_prefixes() {
+        let partial = PartialHttpRequest::parse_with_comment_prefixes(
+            "GET https://example.com HTTP/1.1\n; note\n# x-key: 123",
+            &[";"],
+        )
+        .unwrap();
+
+        assert_eq!(vec!["# x-key: 123"], partial.header_strs());
+        assert_eq!(&vec![33..40], partial.comment_spans());
+    }
+
+    #[test]
+    fn returns_all_spans() {
+        let partial = PartialHttpRequest::parse("GET https://example.com\nx-key: 123").unwrap();
+
+        assert_eq!(
+            PartialRequestSpans {
+                method: Some(0..3),
+                uri: Some(4..23),
+                http_version: None,
+                headers: vec![24..34],
+                body: None,
+            },
+            partial.spans()
+        );
+    }
+
     #[test]
     fn implements_default() {
         let partial = PartialHttpRequest::default();
@@ -0,0 +1,474 @@
+use core::fmt;
+use std::ops::Range;
+
+use crate::{
+    error::Error,
+    models::{HttpHeader, HttpResponse},
+    span::{
+        Span, bom_len, get_line_spans, header_key_span, header_value_span, is_blank_line,
+        offset_span,
+    },
+};
+
+/// A partial HTTP response that might not conform to HTTP spec
+///
+/// A templated or truncated response without a version is an example use case.
+///
+/// ```skip
+/// 200
+/// Content-Type: text/html
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct PartialHttpResponse<'http_message> {
+    message: &'http_message str,
+    http_version: Option<Range<usize>>,
+    status_code: Option<Range<usize>>,
+    reason: Option<Range<usize>>,
+    headers: Vec<Range<usize>>,
+    body: Option<Range<usize>>,
+}
+
+impl<'http_message> fmt::Display for PartialHttpResponse<'http_message> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl<'http_message> PartialHttpResponse<'http_message> {
+    pub fn parse(message: &'http_message str) -> Result<Self, Error> {
+        parse_response(message)
+    }
+
+    pub fn parsed(
+        message: &'http_message str,
+        http_version: Option<Range<usize>>,
+        status_code: Option<Range<usize>>,
+        reason: Option<Range<usize>>,
+        headers: Vec<Range<usize>>,
+        body: Option<Range<usize>>,
+    ) -> Self {
+        let partial = Self {
+            message,
+            http_version,
+            status_code,
+            reason,
+            headers,
+            body,
+        };
+
+        partial.verify_spans();
+
+        partial
+    }
+
+    /// Verify all the spans in the struct are valid
+    ///
+    /// - Aren't out of bounds of the message
+    /// - Parts aren't overlapping or out of order
+    ///
+    /// Parsing only creates valid spans so these are debug assertions.
+    fn verify_spans(&self) {
+        self.http_version.as_ref().inspect(|span| {
+            debug_assert!(span.start < span.end);
+            assert_text_span(self.message(), span);
+        });
+
+        self.status_code.as_ref().inspect(|span| {
+            debug_assert!(span.start < span.end);
+            assert_text_span(self.message(), span);
+
+            if let Some(http_version) = self.version_span() {
+                debug_assert!(
+                    http_version.end < span.start,
+                    "status code {span:?} and http version {http_version:?} spans conflict"
+                );
+            }
+        });
+
+        self.reason.as_ref().inspect(|span| {
+            debug_assert!(span.start < span.end);
+            assert_text_span(self.message(), span);
+
+            if let Some(previous) = self
+                .status_code_span()
+                .as_ref()
+                .or(self.version_span().as_ref())
+            {
+                debug_assert!(
+                    previous.end < span.start,
+                    "reason {span:?} and {previous:?} spans conflict"
+                );
+            }
+        });
+
+        for span in self.header_spans().iter() {
+            debug_assert!(span.start < span.end);
+            assert_text_span(self.message(), span);
+        }
+
+        self.body.as_ref().inspect(|span| {
+            debug_assert!(span.start < span.end);
+            assert_text_span(self.message(), span);
+        });
+    }
+
+    /// Get the original HTTP response message text
+    pub fn message(&self) -> &str {
+        self.message
+    }
+
+    /// Get the text span of the http version, if defined
+    pub fn version_span(&self) -> &Option<Range<usize>> {
+        &self.http_version
+    }
+
+    /// Get the string text of the http version, if defined
+    pub fn version_str(&self) -> Option<&str> {
+        self.http_version
+            .as_ref()
+            .map(|span| self.slice_message(span))
+    }
+
+    /// Get the text span of the status code, if defined
+    pub fn status_code_span(&self) -> &Option<Range<usize>> {
+        &self.status_code
+    }
+
+    /// Get the string text of the status code, if defined
+    pub fn status_code_str(&self) -> Option<&str> {
+        self.status_code
+            .as_ref()
+            .map(|span| self.slice_message(span))
+    }
+
+    /// Get the text span of the reason phrase, if defined
+    pub fn reason_span(&self) -> &Option<Range<usize>> {
+        &self.reason
+    }
+
+    /// Get the string text of the reason phrase, if defined
+    pub fn reason_str(&self) -> Option<&str> {
+        self.reason.as_ref().map(|span| self.slice_message(span))
+    }
+
+    /// Get a list of the header line text spans
+    pub fn header_spans(&self) -> &Vec<Range<usize>> {
+        &self.headers
+    }
+
+    /// Get each header key in order, including duplicates
+    pub fn header_keys(&self) -> Vec<&str> {
+        self.headers
+            .iter()
+            .map(|span| self.slice_message(&header_key_span(self.message, span)))
+            .collect()
+    }
+
+    /// Get a list of the string text header lines
+    pub fn header_strs(&self) -> Vec<&str> {
+        self.headers
+            .iter()
+            .map(|span| self.slice_message(span))
+            .collect()
+    }
+
+    /// Get the text span of a header line by key, if defined
+    pub fn header_span(&self, key: &str) -> Option<&Range<usize>> {
+        self.headers
+            .iter()
+            .find(|span| self.slice_message(span).starts_with(&format!("{key}:")))
+    }
+
+    /// Get the string text of a header by key, if defined
+    pub fn header_str(&self, key: &str) -> Option<&str> {
+        self.header_span(key).map(|span| self.slice_message(span))
+    }
+
+    /// Get the trimmed string text and text span of a header value by key, if defined
+    pub fn header_value(&self, key: &str) -> Option<(&str, Range<usize>)> {
+        self.header_span(key)
+            .and_then(|span| header_value_span(self.message, span))
+            .map(|span| (self.slice_message(&span), span))
+    }
+
+    /// Get the raw string text of the body, if defined
+    ///
+    /// This is exactly the text after the separator line, including any trailing
+    /// line terminators. See [PartialHttpResponse::body_trimmed] to exclude them.
+    pub fn body_str(&self) -> Option<&str> {
+        self.body.as_ref().map(|span| &self.message[span.clone()])
+    }
+
+    /// Get the string text of the body without trailing `\r` and `\n`, if defined
+    pub fn body_trimmed(&self) -> Option<&str> {
+        self.body_str()
+            .map(|body| body.trim_end_matches(['\r', '\n']))
+    }
+
+    /// Return a slice of the message string
+    fn slice_message(&self, span: &Span) -> &str {
+        &self.message[span.clone()]
+    }
+}
+
+fn assert_text_span(text: &str, span: &Range<usize>) {
+    debug_assert!(
+        text.get(span.clone()).is_some(),
+        "span {span:?} is outside of text bounds"
+    );
+}
+
+fn parse_response(input: &str) -> Result<PartialHttpResponse<'_>, Error> {
+    let bom_len = bom_len(input);
+
+    if input[bom_len..].trim().is_empty() {
+        return Ok(PartialHttpResponse::parsed(
+            input,
+            None,
+            None,
+            None,
+            vec![],
+            None,
+        ));
+    }
+
+    let line_spans = get_line_spans(input);
+
+    let first_empty_line_idx = line_spans
+        .iter()
+        .position(|span| is_blank_line(input, span));
+
+    let head_end = first_empty_line_idx.unwrap_or(line_spans.len());
+
+    let mut head_spans = line_spans[..head_end].iter().cloned();
+
+    let (http_version, status_code, reason) = head_spans
+        .next()
+        .map(|span| {
+            let start = span.start.max(bom_len);
+            let (http_version, status_code, reason) = parse_status_line(&input[start..span.end]);
+
+            (
+                http_version.map(|span| offset_span(span, start)),
+                status_code.map(|span| offset_span(span, start)),
+                reason.map(|span| offset_span(span, start)),
+            )
+        })
+        .unwrap_or((None, None, None));
+
+    let header_spans: Vec<Range<usize>> = head_spans.collect();
+
+    let body_span = first_empty_line_idx.and_then(|idx| {
+        let body_span = line_spans[idx].end..input.len();
+
+        (!body_span.is_empty()).then_some(body_span)
+    });
+
+    Ok(PartialHttpResponse::parsed(
+        input,
+        http_version,
+        status_code,
+        reason,
+        header_spans,
+        body_span,
+    ))
+}
+
+/// Parse the status line of an HTTP response message where any part can be missing
+///
+/// A leading `HTTP/` token is the version and a following all digit token is the
+/// status code. Everything after those is the reason phrase.
+fn parse_status_line(status_line: &str) -> (Option<Span>, Option<Span>, Option<Span>) {
+    let mut tokens = vec![];
+    let mut last_end = 0;
+
+    for (i, c) in status_line.char_indices() {
+        if c.is_whitespace() {
+            if i > last_end {
+                tokens.push(last_end..i);
+            }
+            last_end = i + c.len_utf8();
+        }
+    }
+
+    if last_end < status_line.len() {
+        tokens.push(last_end..status_line.len());
+    }
+
+    let mut tokens = tokens.into_iter().peekable();
+
+    let http_version = tokens.next_if(|span| status_line[span.clone()].starts_with("HTTP/"));
+
+    let status_code = tokens.next_if(|span| {
+        status_line[span.clone()]
+            .bytes()
+            .all(|byte| byte.is_ascii_digit())
+    });
+
+    let consumed_end = status_code
+        .as_ref()
+        .or(http_version.as_ref())
+        .map(|span| span.end)
+        .unwrap_or(0);
+
+    let rest = &status_line[consumed_end..];
+    let start = consumed_end + (rest.len() - rest.trim_start().len());
+    let end = consumed_end + rest.trim_end().len();
+
+    let reason = (start < end).then_some(start..end);
+
+    (http_version, status_code, reason)
+}
+
+impl<'a> TryFrom<PartialHttpResponse<'a>> for HttpResponse {
+    type Error = Error;
+
+    /// A missing http version defaults to `HTTP/1.1`
+    fn try_from(value: PartialHttpResponse<'a>) -> Result<Self, Self::Error> {
+        let status_code = value
+            .status_code_str()
+            .ok_or(Error::missing_required("status_code"))?;
+
+        let mut response = HttpResponse::new(
+            status_code.parse()?,
+            value
+                .header_strs()
+                .into_iter()
+                .map(HttpHeader::parse)
+                .collect::<Result<Vec<HttpHeader>, Error>>()?,
+            value.body_str(),
+        );
+
+        response.reason = value.reason_str().map(|reason| reason.to_string());
+
+        if let Some(http_version) = value.version_str() {
+            response.http_version = http_version.into();
+        }
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::reversed_empty_ranges, clippy::single_range_in_vec_init)]
+mod tests {
+    use crate::{
+        error::Error,
+        models::{HttpResponse, HttpStatusCode, PartialHttpResponse},
+    };
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn verifies_out_of_bounds_http_version_span() {
+        PartialHttpResponse::parsed("", Some(1..2), None, None, vec![], None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn verifies_inverted_status_code_span() {
+        PartialHttpResponse::parsed("200", None, Some(2..1), None, vec![], None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn verifies_reason_overlaps_status_code() {
+        PartialHttpResponse::parsed("200 OK", None, Some(0..3), Some(2..6), vec![], None);
+    }
+
+    #[test]
+    fn parses_empty_message() {
+        assert_eq!(
+            Ok(PartialHttpResponse::parsed(
+                " \n",
+                None,
+                None,
+                None,
+                vec![],
+                None
+            )),
+            PartialHttpResponse::parse(" \n")
+        );
+    }
+
+    #[test]
+    fn parses_response_without_version() {
+        let message = "200\nContent-Type: text/html";
+
+        let partial = PartialHttpResponse::parse(message).unwrap();
+
+        assert_eq!(
+            PartialHttpResponse::parsed(message, None, Some(0..3), None, vec![4..27], None),
+            partial
+        );
+        assert_eq!(Some("200"), partial.status_code_str());
+        assert_eq!(
+            Some(("text/html", 18..27)),
+            partial.header_value("Content-Type")
+        );
+    }
+
+    #[test]
+    fn parses_full_response() {
+        let message = "HTTP/1.1 404 Not Found\r\nx-key: 123\r\n\r\nmissing";
+
+        let partial = PartialHttpResponse::parse(message).unwrap();
+
+        assert_eq!(
+            PartialHttpResponse::parsed(
+                message,
+                Some(0..8),
+                Some(9..12),
+                Some(13..22),
+                vec![24..36],
+                Some(38..45)
+            ),
+            partial
+        );
+        assert_eq!(Some("Not Found"), partial.reason_str());
+        assert_eq!(Some("missing"), partial.body_str());
+    }
+
+    #[test]
+    fn parses_version_with_reason_and_no_status_code() {
+        let partial = PartialHttpResponse::parse("HTTP/1.1 {{status}}\n").unwrap();
+
+        assert_eq!(Some("HTTP/1.1"), partial.version_str());
+        assert_eq!(None, partial.status_code_str());
+        assert_eq!(Some("{{status}}"), partial.reason_str());
+    }
+
+    #[test]
+    fn converts_to_http_response() {
+        let partial = PartialHttpResponse::parse("201 Created\nx-key: 123\n\nbody").unwrap();
+
+        let response: HttpResponse = partial.try_into().unwrap();
+
+        assert_eq!(HttpStatusCode::new(201), response.status_code);
+        assert_eq!(Some("Created".to_string()), response.reason);
+        assert_eq!("HTTP/1.1", response.http_version.to_string());
+        assert_eq!("123", response.get_header("x-key").unwrap().value());
+        assert_eq!(Some("body".to_string()), response.body);
+    }
+
+    #[test]
+    fn converts_to_errors() {
+        let partial = PartialHttpResponse::parse("HTTP/1.1\n\n").unwrap();
+        let response: Result<HttpResponse, Error> = partial.try_into();
+
+        assert_eq!(
+            Err(Error::missing_required("status_code")),
+            response.map(|_| ())
+        );
+
+        let partial = PartialHttpResponse::parse("99999 Huge\n\n").unwrap();
+        let response: Result<HttpResponse, Error> = partial.try_into();
+
+        assert_eq!(
+            Err(Error::invalid_status_code("99999")),
+            response.map(|_| ())
+        );
+    }
+}
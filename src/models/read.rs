@@ -0,0 +1,670 @@
+use std::io::{BufRead, ErrorKind, Read};
+
+use crate::{
+    error::Error,
+    models::{
+        HttpHeader, HttpRequest, ParseLimits, ParsedHttpRequest, Uri,
+        headers::{find_header, parse_folded_header},
+    },
+};
+
+/// The number of bytes [RequestParser] tries to read at a time
+const READ_CHUNK_LEN: usize = 4096;
+
+/// A parser that reads requests from a stream in to a reused buffer
+///
+/// Bytes after a complete request, like a pipelined request, are kept in the
+/// buffer for the next call so no allocation is needed per request.
+///
+/// ```
+/// use http_message::models::{ParsedHttpRequest, RequestParser};
+///
+/// let mut stream = "GET /a HTTP/1.1\r\n\r\nGET /b HTTP/1.1\r\n\r\n".as_bytes();
+/// let mut parser = RequestParser::new();
+/// let mut buf = Vec::new();
+/// let mut uris = vec![];
+///
+/// while let Some(len) = parser.parse_into(&mut buf, &mut stream).unwrap() {
+///     let message = std::str::from_utf8(&buf[..len]).unwrap();
+///     let request = ParsedHttpRequest::parse(message).unwrap();
+///     uris.push(request.uri_str().to_string());
+/// }
+///
+/// assert_eq!(uris, vec!["/a", "/b"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RequestParser {
+    limits: ParseLimits,
+    consumed: usize,
+}
+
+impl RequestParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a parser that returns [Error::LimitExceeded] when a request exceeds limits
+    pub fn with_limits(limits: ParseLimits) -> Self {
+        Self {
+            limits,
+            consumed: 0,
+        }
+    }
+
+    /// Read until buf starts with a complete request, returning its length in bytes
+    ///
+    /// The request is `buf[..len]` until the next call, which removes it from the
+    /// start of buf. A request is complete after the blank line plus a body of
+    /// `Content-Length` bytes if defined. Empty lines before a request are removed
+    /// from buf. Returns `None` if the stream ends cleanly between requests.
+    ///
+    /// Returns [Error::UnexpectedEof] if the stream ends part way through a body
+    /// and [Error::UnsupportedTransferEncoding] if the body uses a `Transfer-Encoding`.
+    pub fn parse_into<R: Read>(
+        &mut self,
+        buf: &mut Vec<u8>,
+        reader: &mut R,
+    ) -> Result<Option<usize>, Error> {
+        buf.drain(..self.consumed.min(buf.len()));
+        self.consumed = 0;
+
+        loop {
+            let noise = buf
+                .iter()
+                .position(|byte| !matches!(byte, b'\r' | b'\n'))
+                .unwrap_or(buf.len());
+            buf.drain(..noise);
+
+            if let Some(head_end) = find_head_end(buf) {
+                let head = std::str::from_utf8(&buf[..head_end]).map_err(|error| Error::Io {
+                    kind: ErrorKind::InvalidData,
+                    message: error.to_string(),
+                })?;
+
+                let content_length = content_length(&parse_head(head, &self.limits)?)?;
+                let len = message_len(head_end, content_length, &self.limits)?;
+
+                if buf.len() >= len {
+                    self.consumed = len;
+                    return Ok(Some(len));
+                }
+
+                if read_chunk(buf, reader)? == 0 {
+                    return Err(Error::UnexpectedEof {
+                        expected: len - head_end,
+                        actual: buf.len() - head_end,
+                    });
+                }
+
+                continue;
+            }
+
+            if buf.len() > self.limits.max_total {
+                return Err(Error::LimitExceeded { which: "max_total" });
+            }
+
+            if read_chunk(buf, reader)? == 0 {
+                if buf.iter().all(|byte| matches!(byte, b'\r' | b'\n')) {
+                    buf.clear();
+                    return Ok(None);
+                }
+
+                return Err(Error::missing_required("empty line"));
+            }
+        }
+    }
+}
+
+impl HttpRequest {
+    /// Read a request from a stream, returning it with the number of bytes consumed
+    ///
+    /// The request line and headers are read up to the blank line, then exactly
+    /// `Content-Length` body bytes if defined. Without a `Content-Length` no body is
+    /// read. Nothing after the request is consumed.
+    ///
+    /// Returns [Error::UnexpectedEof] if the stream ends before the whole body is read
+    /// and [Error::UnsupportedTransferEncoding] if the body uses a `Transfer-Encoding`.
+    pub fn read_from<R: BufRead>(reader: &mut R) -> Result<(HttpRequest, usize), Error> {
+        Self::read_from_with_limits(reader, ParseLimits::default())
+    }
+
+    /// Read a request from a stream, returning [Error::LimitExceeded] if it exceeds any limit
+    ///
+    /// No more than `max_total` bytes of the head are buffered and the declared
+    /// `Content-Length` is checked against `max_total` before the body is read.
+    pub fn read_from_with_limits<R: BufRead>(
+        reader: &mut R,
+        limits: ParseLimits,
+    ) -> Result<(HttpRequest, usize), Error> {
+        let mut head = vec![];
+
+        loop {
+            let read = reader
+                .by_ref()
+                .take(head_read_limit(head.len(), &limits))
+                .read_until(b'\n', &mut head)?;
+
+            if head.len() > limits.max_total {
+                return Err(Error::LimitExceeded { which: "max_total" });
+            }
+
+            if read == 0 || is_head_end(&head, read) {
+                break;
+            }
+        }
+
+        let head = head_to_string(head)?;
+        let mut request = parse_head(&head, &limits)?;
+
+        let content_length = content_length(&request)?;
+        let consumed = message_len(head.len(), content_length, &limits)?;
+
+        if content_length > 0 {
+            let mut body = Vec::new();
+            reader
+                .by_ref()
+                .take(content_length as u64)
+                .read_to_end(&mut body)?;
+            request.body = Some(body_from_bytes(body, content_length)?);
+        }
+
+        Ok((request, consumed))
+    }
+}
+
+#[cfg(feature = "async")]
+impl HttpRequest {
+    /// Read a request from an async stream
+    ///
+    /// This reads the same way as [HttpRequest::read_from].
+    pub async fn read_async<R>(reader: &mut R) -> Result<(HttpRequest, usize), Error>
+    where
+        R: tokio::io::AsyncBufRead + Unpin,
+    {
+        Self::read_async_with_limits(reader, ParseLimits::default()).await
+    }
+
+    /// Read a request from an async stream, returning [Error::LimitExceeded] if it
+    /// exceeds any limit
+    ///
+    /// This reads the same way as [HttpRequest::read_from_with_limits].
+    pub async fn read_async_with_limits<R>(
+        reader: &mut R,
+        limits: ParseLimits,
+    ) -> Result<(HttpRequest, usize), Error>
+    where
+        R: tokio::io::AsyncBufRead + Unpin,
+    {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+        let mut head = vec![];
+
+        loop {
+            let read = (&mut *reader)
+                .take(head_read_limit(head.len(), &limits))
+                .read_until(b'\n', &mut head)
+                .await?;
+
+            if head.len() > limits.max_total {
+                return Err(Error::LimitExceeded { which: "max_total" });
+            }
+
+            if read == 0 || is_head_end(&head, read) {
+                break;
+            }
+        }
+
+        let head = head_to_string(head)?;
+        let mut request = parse_head(&head, &limits)?;
+
+        let content_length = content_length(&request)?;
+        let consumed = message_len(head.len(), content_length, &limits)?;
+
+        if content_length > 0 {
+            let mut body = Vec::new();
+            (&mut *reader)
+                .take(content_length as u64)
+                .read_to_end(&mut body)
+                .await?;
+            request.body = Some(body_from_bytes(body, content_length)?);
+        }
+
+        Ok((request, consumed))
+    }
+}
+
+/// Check if the last read line is the blank line ending the head
+///
+/// A blank first line is skipped as leading noise.
+fn is_head_end(head: &[u8], read: usize) -> bool {
+    let line = &head[head.len() - read..];
+
+    matches!(line, b"\n" | b"\r\n") && head.len() > read
+}
+
+/// Get how many more head bytes to read, one past `max_total` so going over is detected
+fn head_read_limit(head_len: usize, limits: &ParseLimits) -> u64 {
+    (limits.max_total.saturating_sub(head_len) as u64).saturating_add(1)
+}
+
+/// Convert the head bytes to a string, returning an [ErrorKind::InvalidData] error if
+/// they aren't UTF-8
+fn head_to_string(head: Vec<u8>) -> Result<String, Error> {
+    String::from_utf8(head).map_err(|error| Error::Io {
+        kind: ErrorKind::InvalidData,
+        message: error.to_string(),
+    })
+}
+
+/// Get the end of the blank line ending the head, if it's been read
+///
+/// Blank lines before the request line are skipped as leading noise.
+fn find_head_end(buf: &[u8]) -> Option<usize> {
+    let start = buf.iter().position(|byte| !matches!(byte, b'\r' | b'\n'))?;
+    let mut line_start = start;
+
+    for (idx, byte) in buf.iter().enumerate().skip(start) {
+        if *byte == b'\n' {
+            if matches!(&buf[line_start..=idx], b"\n" | b"\r\n") {
+                return Some(idx + 1);
+            }
+
+            line_start = idx + 1;
+        }
+    }
+
+    None
+}
+
+/// Read a chunk from reader on to the end of buf, returning the number of bytes read
+fn read_chunk<R: Read>(buf: &mut Vec<u8>, reader: &mut R) -> Result<usize, Error> {
+    let len = buf.len();
+    buf.resize(len + READ_CHUNK_LEN, 0);
+
+    loop {
+        match reader.read(&mut buf[len..]) {
+            Ok(read) => {
+                buf.truncate(len + read);
+                return Ok(read);
+            }
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => {
+                buf.truncate(len);
+                return Err(error.into());
+            }
+        }
+    }
+}
+
+/// Parse the request line and headers without a body
+fn parse_head(head: &str, limits: &ParseLimits) -> Result<HttpRequest, Error> {
+    let parsed = ParsedHttpRequest::from_str_with_limits(
+        head.trim_start_matches(['\r', '\n']),
+        limits.clone(),
+    )?;
+
+    Ok(HttpRequest {
+        uri: Uri::parse(parsed.uri_str())?,
+        method: parsed.method_str().into(),
+        http_version: parsed.http_version_str().into(),
+        headers: parsed
+            .header_strs()
+            .into_iter()
+            .map(parse_folded_header)
+            .collect::<Result<Vec<HttpHeader>, Error>>()?,
+        body: None,
+    })
+}
+
+/// Get the `Content-Length` of the body, or `0` if not defined
+///
+/// Bodies framed by a `Transfer-Encoding` like `chunked` aren't supported, so they
+/// return [Error::UnsupportedTransferEncoding] instead of leaving the encoded body
+/// to be read as the next request.
+fn content_length(request: &HttpRequest) -> Result<usize, Error> {
+    if let Some(header) = find_header(&request.headers, "Transfer-Encoding") {
+        return Err(Error::UnsupportedTransferEncoding {
+            encoding: header.value().to_string(),
+        });
+    }
+
+    match find_header(&request.headers, "Content-Length") {
+        Some(header) => header
+            .value()
+            .parse()
+            .map_err(|_| Error::invalid_header(header.key())),
+        None => Ok(0),
+    }
+}
+
+/// Get the length of a head plus a body, returning [Error::LimitExceeded] past `max_total`
+fn message_len(
+    head_len: usize,
+    content_length: usize,
+    limits: &ParseLimits,
+) -> Result<usize, Error> {
+    head_len
+        .checked_add(content_length)
+        .filter(|len| *len <= limits.max_total)
+        .ok_or(Error::LimitExceeded { which: "max_total" })
+}
+
+/// Check the whole body was read and is valid UTF-8
+fn body_from_bytes(body: Vec<u8>, content_length: usize) -> Result<String, Error> {
+    if body.len() < content_length {
+        return Err(Error::UnexpectedEof {
+            expected: content_length,
+            actual: body.len(),
+        });
+    }
+
+    String::from_utf8(body).map_err(|_| Error::InvalidBody)
+}
+
+#[cfg(test)]
+mod read_tests {
+    use std::io::BufReader;
+
+    use super::*;
+
+    const MESSAGE: &str = "POST https://example.com/ HTTP/1.1\r\n\
+                           Content-Length: 4\r\n\
+                           \r\n\
+                           bodyGET https://example.com/next HTTP/1.1\r\n\r\n";
+
+    #[test]
+    fn test_read_from() {
+        let mut reader = BufReader::new(MESSAGE.as_bytes());
+
+        let (request, consumed) = HttpRequest::read_from(&mut reader).unwrap();
+
+        assert_eq!(request.method, "POST".into());
+        assert_eq!(request.uri, "https://example.com/".into());
+        assert_eq!(
+            request.headers,
+            vec![HttpHeader::new("Content-Length", "4")]
+        );
+        assert_eq!(request.body, Some("body".to_string()));
+        assert_eq!(consumed, 61);
+
+        let (request, consumed) = HttpRequest::read_from(&mut reader).unwrap();
+
+        assert_eq!(request.uri, "https://example.com/next".into());
+        assert_eq!(request.body, None);
+        assert_eq!(consumed, MESSAGE.len() - 61);
+    }
+
+    #[test]
+    fn test_read_from_origin_form() {
+        let mut reader = BufReader::new("GET /users?id=1 HTTP/1.1\r\nHost: a\r\n\r\n".as_bytes());
+
+        let (request, _) = HttpRequest::read_from(&mut reader).unwrap();
+
+        assert!(request.uri.is_relative());
+        assert_eq!(request.uri.path(), "/users");
+        assert_eq!(request.request_target(), "/users?id=1");
+
+        let mut reader = BufReader::new("GET / HTTP/1.1\r\n\r\n".as_bytes());
+
+        let (request, _) = HttpRequest::read_from(&mut reader).unwrap();
+
+        assert_eq!(request.request_target(), "/");
+    }
+
+    #[test]
+    fn test_read_from_errors() {
+        let mut reader = BufReader::new("".as_bytes());
+        assert_eq!(
+            HttpRequest::read_from(&mut reader),
+            Err(Error::EmptyHttpMessage)
+        );
+
+        let mut reader = BufReader::new(
+            "GET https://example.com/ HTTP/1.1\r\nContent-Length: x\r\n\r\n".as_bytes(),
+        );
+        assert_eq!(
+            HttpRequest::read_from(&mut reader),
+            Err(Error::invalid_header("Content-Length"))
+        );
+
+        let mut reader = BufReader::new(
+            "GET https://example.com/ HTTP/1.1\r\nContent-Length: 10\r\n\r\nabc".as_bytes(),
+        );
+        assert_eq!(
+            HttpRequest::read_from(&mut reader),
+            Err(Error::UnexpectedEof {
+                expected: 10,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_from_unterminated_line_limit() {
+        let limits = ParseLimits {
+            max_total: 1024,
+            ..Default::default()
+        };
+
+        let mut reader = BufReader::new(std::io::repeat(b'a'));
+        assert_eq!(
+            HttpRequest::read_from_with_limits(&mut reader, limits),
+            Err(Error::LimitExceeded { which: "max_total" })
+        );
+    }
+
+    #[test]
+    fn test_read_from_content_length_limits() {
+        let mut reader = BufReader::new(
+            "POST https://example.com/ HTTP/1.1\r\nContent-Length: 18446744073709551615\r\n\r\n"
+                .as_bytes(),
+        );
+        assert_eq!(
+            HttpRequest::read_from(&mut reader),
+            Err(Error::LimitExceeded { which: "max_total" })
+        );
+
+        let limits = ParseLimits {
+            max_total: 64,
+            ..Default::default()
+        };
+
+        let mut reader = BufReader::new(
+            "POST https://example.com/ HTTP/1.1\r\nContent-Length: 1000\r\n\r\n".as_bytes(),
+        );
+        assert_eq!(
+            HttpRequest::read_from_with_limits(&mut reader, limits.clone()),
+            Err(Error::LimitExceeded { which: "max_total" })
+        );
+
+        let mut reader = BufReader::new(MESSAGE.as_bytes());
+        let (request, consumed) = HttpRequest::read_from_with_limits(&mut reader, limits).unwrap();
+        assert_eq!(request.body, Some("body".to_string()));
+        assert_eq!(consumed, 61);
+    }
+
+    #[test]
+    fn test_read_from_transfer_encoding() {
+        let mut reader = BufReader::new(
+            "POST https://example.com/ HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n0\r\n\r\n"
+                .as_bytes(),
+        );
+        assert_eq!(
+            HttpRequest::read_from(&mut reader),
+            Err(Error::UnsupportedTransferEncoding {
+                encoding: "chunked".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_from_without_content_length() {
+        let mut reader = BufReader::new(
+            "POST https://example.com/ HTTP/1.1\r\nx-key: 1\r\n\r\nnot a body".as_bytes(),
+        );
+
+        let (request, consumed) = HttpRequest::read_from(&mut reader).unwrap();
+
+        assert_eq!(request.body, None);
+        assert_eq!(consumed, 48);
+
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "not a body");
+    }
+
+    /// A reader that returns a few bytes at a time like a slow socket
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = self.0.len().min(buf.len()).min(5);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn test_request_parser() {
+        let mut reader = Trickle(MESSAGE.as_bytes());
+        let mut parser = RequestParser::new();
+        let mut buf = Vec::new();
+
+        let len = parser.parse_into(&mut buf, &mut reader).unwrap().unwrap();
+        let parsed = ParsedHttpRequest::parse(std::str::from_utf8(&buf[..len]).unwrap()).unwrap();
+
+        assert_eq!(len, 61);
+        assert_eq!(parsed.method_str(), "POST");
+        assert_eq!(parsed.body_str(), Some("body"));
+
+        let len = parser.parse_into(&mut buf, &mut reader).unwrap().unwrap();
+        let parsed = ParsedHttpRequest::parse(std::str::from_utf8(&buf[..len]).unwrap()).unwrap();
+
+        assert_eq!(len, MESSAGE.len() - 61);
+        assert_eq!(parsed.uri_str(), "https://example.com/next");
+
+        assert_eq!(parser.parse_into(&mut buf, &mut reader), Ok(None));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_request_parser_leading_empty_lines() {
+        let mut reader =
+            "\r\n\r\nGET /a HTTP/1.1\r\n\r\n\r\nGET /b HTTP/1.1\r\n\r\n\r\n".as_bytes();
+        let mut parser = RequestParser::new();
+        let mut buf = Vec::new();
+
+        let len = parser.parse_into(&mut buf, &mut reader).unwrap().unwrap();
+        let parsed = ParsedHttpRequest::parse(std::str::from_utf8(&buf[..len]).unwrap()).unwrap();
+        assert_eq!(parsed.uri_str(), "/a");
+
+        let len = parser.parse_into(&mut buf, &mut reader).unwrap().unwrap();
+        let parsed = ParsedHttpRequest::parse(std::str::from_utf8(&buf[..len]).unwrap()).unwrap();
+        assert_eq!(parsed.uri_str(), "/b");
+
+        assert_eq!(parser.parse_into(&mut buf, &mut reader), Ok(None));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_request_parser_errors() {
+        let mut parser = RequestParser::new();
+        let mut buf = Vec::new();
+
+        let mut reader =
+            "GET https://example.com/ HTTP/1.1\r\nContent-Length: 10\r\n\r\nabc".as_bytes();
+        assert_eq!(
+            parser.parse_into(&mut buf, &mut reader),
+            Err(Error::UnexpectedEof {
+                expected: 10,
+                actual: 3
+            })
+        );
+
+        let mut buf = Vec::new();
+        let mut reader = "GET https://example.com/ HTTP/1.1\r\nx-key: 1\r\n".as_bytes();
+        assert_eq!(
+            parser.parse_into(&mut buf, &mut reader),
+            Err(Error::missing_required("empty line"))
+        );
+
+        let mut buf = Vec::new();
+        let mut reader =
+            "POST / HTTP/1.1\r\nContent-Length: 18446744073709551615\r\n\r\n".as_bytes();
+        assert_eq!(
+            parser.parse_into(&mut buf, &mut reader),
+            Err(Error::LimitExceeded { which: "max_total" })
+        );
+
+        let mut buf = Vec::new();
+        let mut reader =
+            "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\n".as_bytes();
+        assert_eq!(
+            parser.parse_into(&mut buf, &mut reader),
+            Err(Error::UnsupportedTransferEncoding {
+                encoding: "chunked".to_string()
+            })
+        );
+
+        let mut parser = RequestParser::with_limits(ParseLimits {
+            max_total: 40,
+            ..Default::default()
+        });
+        let mut buf = Vec::new();
+        let mut reader = MESSAGE.as_bytes();
+        assert_eq!(
+            parser.parse_into(&mut buf, &mut reader),
+            Err(Error::LimitExceeded { which: "max_total" })
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_read_async_unterminated_line_limit() {
+        let limits = ParseLimits {
+            max_total: 1024,
+            ..Default::default()
+        };
+
+        let mut reader = tokio::io::BufReader::new(tokio::io::repeat(b'a'));
+        assert_eq!(
+            HttpRequest::read_async_with_limits(&mut reader, limits).await,
+            Err(Error::LimitExceeded { which: "max_total" })
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_read_async() {
+        let mut reader = tokio::io::BufReader::new(MESSAGE.as_bytes());
+
+        let (request, consumed) = HttpRequest::read_async(&mut reader).await.unwrap();
+
+        assert_eq!(request.method, "POST".into());
+        assert_eq!(request.body, Some("body".to_string()));
+        assert_eq!(consumed, 61);
+
+        let (request, _) = HttpRequest::read_async(&mut reader).await.unwrap();
+
+        assert_eq!(request.uri, "https://example.com/next".into());
+
+        let mut reader = tokio::io::BufReader::new("GET /users?id=1 HTTP/1.1\r\n\r\n".as_bytes());
+
+        let (request, _) = HttpRequest::read_async(&mut reader).await.unwrap();
+
+        assert_eq!(request.uri.path(), "/users");
+        assert_eq!(request.request_target(), "/users?id=1");
+
+        let mut reader = tokio::io::BufReader::new(
+            "GET https://example.com/ HTTP/1.1\r\nContent-Length: 10\r\n\r\nabc".as_bytes(),
+        );
+        assert_eq!(
+            HttpRequest::read_async(&mut reader).await,
+            Err(Error::UnexpectedEof {
+                expected: 10,
+                actual: 3
+            })
+        );
+    }
+}
@@ -1,12 +1,24 @@
+use core::fmt;
+use std::{collections::HashMap, str::FromStr};
+
+use url::form_urlencoded;
+
 use crate::{
     error::Error,
     models::{
-        HttpBody, HttpHeader, HttpVersion, ParsedHttpRequest, PartialHttpRequest, PossibleHttpBody,
-        Uri,
+        AcceptRange, ByteRange, ForwardedElement, HttpBody, HttpHeader, HttpVersion, MediaType,
+        ParsedHttpRequest, PartialHttpRequest, PossibleHttpBody, RequestDiff, SmugglingRisk,
+        StandardHeader, Uri,
+        connection::{connection_has_token, should_keep_alive, strip_hop_by_hop},
+        diff::diff_headers,
+        headers::{filter_headers, find_header, fold_headers, parse_folded_header},
+        smuggling::find_smuggling_risks,
+        uri::has_http_scheme,
     },
+    span::{get_line_spans, trim_line_ending},
 };
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum HttpMethod {
     GET,
     POST,
@@ -33,7 +45,38 @@ impl From<&str> for HttpMethod {
     }
 }
 
-#[derive(Debug, PartialEq)]
+impl HttpMethod {
+    pub fn as_str(&self) -> &str {
+        match self {
+            HttpMethod::GET => "GET",
+            HttpMethod::POST => "POST",
+            HttpMethod::PUT => "PUT",
+            HttpMethod::PATCH => "PATCH",
+            HttpMethod::DELETE => "DELETE",
+            HttpMethod::HEAD => "HEAD",
+            HttpMethod::OPTIONS => "OPTIONS",
+            HttpMethod::Other(method) => method,
+        }
+    }
+}
+
+impl fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Headers redacted by [HttpRequest::redacted_default]
+pub const SENSITIVE_HEADERS: [&str; 4] = [
+    "Authorization",
+    "Cookie",
+    "Set-Cookie",
+    "Proxy-Authorization",
+];
+
+const REDACTED_VALUE: &str = "***";
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct HttpRequest {
     pub uri: Uri,
     pub method: HttpMethod,
@@ -63,14 +106,69 @@ impl HttpRequest {
         }
     }
 
+    /// Get a spec valid `HTTP/1.1` request with only a `Host` header derived from the uri
+    pub fn minimal(method: HttpMethod, uri: &str) -> Self {
+        let uri = Uri::new(uri);
+
+        let host = match (uri.host(), uri.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_string(),
+            (None, _) => String::new(),
+        };
+
+        Self {
+            uri,
+            method,
+            http_version: Default::default(),
+            headers: vec![HttpHeader::new("Host", &host)],
+            body: None,
+        }
+    }
+
+    /// Get the request with the body replaced
+    pub fn with_body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Get the request with a header set or updated by key
+    pub fn with_header(mut self, key: &str, value: &str) -> Self {
+        self.set_header(key, value);
+        self
+    }
+
+    /// Get the request with the method replaced
+    pub fn with_method(mut self, method: HttpMethod) -> Self {
+        self.method = method;
+        self
+    }
+
     pub fn headers(&self) -> &Vec<HttpHeader> {
         &self.headers
     }
 
+    /// Get the headers in insertion order
+    ///
+    /// Parsed headers keep their message order and [HttpRequest::set_header] updates
+    /// existing headers in place, so order is preserved when serializing.
+    pub fn headers_in_order(&self) -> &[HttpHeader] {
+        &self.headers
+    }
+
+    /// Get each header key in order, including duplicates
+    pub fn header_keys(&self) -> Vec<&str> {
+        self.headers.iter().map(|header| header.key()).collect()
+    }
+
     pub fn get_header(&self, key: &str) -> Option<&HttpHeader> {
         self.headers.iter().find(|header| header.key() == key)
     }
 
+    /// Get a well-known header, matching its key ignoring ASCII case
+    pub fn get_standard_header(&self, header: StandardHeader) -> Option<&HttpHeader> {
+        find_header(&self.headers, header.as_str())
+    }
+
     /// Set or update header by key
     pub fn set_header(&mut self, key: &str, value: &str) {
         let existing_header: Option<&mut HttpHeader> = self.get_header_mut(key);
@@ -81,9 +179,448 @@ impl HttpRequest {
         }
     }
 
+    /// Replace all headers, keeping the order given
+    pub fn set_headers(&mut self, headers: Vec<HttpHeader>) {
+        self.headers = headers;
+    }
+
+    /// Copy headers from another request, e.g. to apply default headers
+    ///
+    /// Keys are matched ignoring ASCII case. With `overwrite` every existing header
+    /// sharing a key with `other` is removed first, otherwise only headers with
+    /// missing keys are copied. Copied headers are appended in order.
+    pub fn merge_headers_from(&mut self, other: &HttpRequest, overwrite: bool) {
+        let copied: Vec<HttpHeader> = other
+            .headers
+            .iter()
+            .filter(|header| overwrite || find_header(&self.headers, header.key()).is_none())
+            .cloned()
+            .collect();
+
+        if overwrite {
+            self.headers
+                .retain(|header| find_header(&other.headers, header.key()).is_none());
+        }
+
+        self.headers.extend(copied);
+    }
+
+    /// Remove all headers
+    pub fn clear_headers(&mut self) {
+        self.headers.clear();
+    }
+
     pub fn get_header_mut(&mut self, key: &str) -> Option<&mut HttpHeader> {
         self.headers.iter_mut().find(|header| header.key() == key)
     }
+
+    /// Collapse all headers matching key in to one with values joined by `, `
+    ///
+    /// The folded header keeps the position and key spelling of the first match.
+    pub fn fold_header(&mut self, key: &str) {
+        fold_headers(&mut self.headers, key);
+    }
+
+    /// Fold every repeated header except `Set-Cookie`, which can't be combined
+    pub fn fold_all_headers(&mut self) {
+        let mut keys: Vec<String> = vec![];
+
+        for header in &self.headers {
+            if !header.has_key("Set-Cookie") && !keys.iter().any(|key| header.has_key(key)) {
+                keys.push(header.key().to_string());
+            }
+        }
+
+        for key in keys {
+            fold_headers(&mut self.headers, &key);
+        }
+    }
+
+    /// Rename the first header matching from, keeping its value and position
+    ///
+    /// Keys are matched ignoring ASCII case. Returns whether a header was renamed.
+    pub fn rename_header(&mut self, from: &str, to: &str) -> bool {
+        match self.headers.iter_mut().find(|header| header.has_key(from)) {
+            Some(header) => {
+                header.set_key(to);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get a header value by key parsed as `T`, if defined
+    ///
+    /// Keys are matched ignoring ASCII case.
+    pub fn header_as<T: FromStr>(&self, key: &str) -> Option<Result<T, T::Err>> {
+        find_header(&self.headers, key).map(|header| header.value().parse())
+    }
+
+    /// Get the uri query parameters grouped by key with percent-encoding decoded
+    ///
+    /// Repeated keys like `?tag=a&tag=b` keep their values in order.
+    pub fn query_map(&self) -> HashMap<String, Vec<String>> {
+        let mut query_map: HashMap<String, Vec<String>> = HashMap::new();
+
+        if let Some(query) = self.uri.query() {
+            for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+                query_map
+                    .entry(key.into_owned())
+                    .or_default()
+                    .push(value.into_owned());
+            }
+        }
+
+        query_map
+    }
+
+    /// Get the request target as it would appear in the request line
+    ///
+    /// This is the absolute-form `https://host/path?q=1` when the uri was written
+    /// with a scheme, otherwise the origin-form path and query, e.g. `/path?q=1`.
+    /// `CONNECT` requests use the authority-form `host:port`, always with a port, and
+    /// a `*` uri is kept as the asterisk-form, e.g. for `OPTIONS *`.
+    pub fn request_target(&self) -> String {
+        if self.uri.raw() == Some("*") {
+            return "*".to_string();
+        }
+
+        if self.method.as_str() == "CONNECT" {
+            let host = self.uri.host().unwrap_or_default();
+
+            return match self.uri.port_or_known_default() {
+                Some(port) => format!("{host}:{port}"),
+                None => host.to_string(),
+            };
+        }
+
+        let origin_form = match self.uri.query() {
+            Some(query) => format!("{}?{query}", self.uri.path()),
+            None => self.uri.path().to_string(),
+        };
+
+        let written_absolute = self.uri.raw().is_none_or(has_http_scheme);
+
+        match self.uri.origin() {
+            Some(origin) if written_absolute => format!("{origin}{origin_form}"),
+            _ => origin_form,
+        }
+    }
+
+    /// Get a stable cache key by hashing the method, normalized uri, and vary header values
+    ///
+    /// The key is 16 lowercase hex digits of a 64-bit FNV-1a hash. Uris are normalized
+    /// with [Uri::canonical_with_sorted_query] so equivalent uris share a key. Vary
+    /// header keys are matched ignoring ASCII case, repeated values are joined with
+    /// `, `, and missing headers have an empty value.
+    pub fn cache_key(&self, vary: &[&str]) -> String {
+        let mut parts = vec![
+            self.method.to_string(),
+            self.uri.canonical_with_sorted_query().to_string(),
+        ];
+
+        let mut vary: Vec<String> = vary.iter().map(|key| key.to_ascii_lowercase()).collect();
+        vary.sort();
+        vary.dedup();
+
+        for vary_key in vary {
+            let values: Vec<&str> = filter_headers(&self.headers, &vary_key)
+                .map(|header| header.value())
+                .collect();
+
+            parts.push(values.join(", "));
+            parts.push(vary_key);
+        }
+
+        format!("{:016x}", fnv1a(&parts))
+    }
+
+    /// Get the byte ranges from the `Range` header, if defined
+    ///
+    /// A malformed header or unit other than `bytes` has no ranges.
+    pub fn ranges(&self) -> Option<Vec<ByteRange>> {
+        find_header(&self.headers, "Range").map(|header| ByteRange::parse_all(header.value()))
+    }
+
+    /// Get the client and proxy addresses from every `X-Forwarded-For` header in order
+    pub fn forwarded_for(&self) -> Vec<String> {
+        filter_headers(&self.headers, "X-Forwarded-For")
+            .flat_map(|header| header.value().split(','))
+            .map(str::trim)
+            .filter(|address| !address.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Get the elements from every RFC 7239 `Forwarded` header in order
+    pub fn forwarded(&self) -> Vec<ForwardedElement> {
+        filter_headers(&self.headers, "Forwarded")
+            .flat_map(|header| ForwardedElement::parse_all(header.value()))
+            .collect()
+    }
+
+    /// Get each line of the body without its line ending
+    ///
+    /// The final line is included whether or not it ends with a newline.
+    pub fn body_lines(&self) -> Vec<&str> {
+        let body = self.body.as_deref().unwrap_or_default();
+
+        get_line_spans(body)
+            .iter()
+            .map(|line| &body[trim_line_ending(body, line)])
+            .collect()
+    }
+
+    /// Get up to `max_bytes` of the body for previews, if defined
+    ///
+    /// A truncated body backs off to the previous char boundary and ends with `…`.
+    pub fn body_preview(&self, max_bytes: usize) -> Option<String> {
+        let body = self.body.as_deref()?;
+
+        if body.len() <= max_bytes {
+            return Some(body.to_string());
+        }
+
+        let mut end = max_bytes;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        Some(format!("{}…", &body[..end]))
+    }
+
+    /// Get the media ranges from the `Accept` header sorted by descending quality
+    ///
+    /// Values from every `Accept` header are combined. Empty if not defined.
+    pub fn accept(&self) -> Vec<AcceptRange> {
+        let value: Vec<&str> = filter_headers(&self.headers, "Accept")
+            .map(|header| header.value())
+            .collect();
+
+        AcceptRange::parse_all(&value.join(","))
+    }
+
+    /// Get the token from an `Authorization: Bearer <token>` header, if defined
+    ///
+    /// The scheme is matched ignoring ASCII case and the token is trimmed.
+    pub fn bearer_token(&self) -> Option<&str> {
+        let value = find_header(&self.headers, "Authorization")?.value().trim();
+        let (scheme, token) = value.split_once([' ', '\t'])?;
+
+        let token = token.trim();
+
+        (scheme.eq_ignore_ascii_case("Bearer") && !token.is_empty()).then_some(token)
+    }
+
+    /// Get the parsed media type from the `Content-Type` header, if defined
+    pub fn content_type(&self) -> Option<MediaType> {
+        find_header(&self.headers, "Content-Type")
+            .and_then(|header| MediaType::parse(header.value()))
+    }
+
+    /// Get the decoded key/value pairs of an `application/x-www-form-urlencoded` body
+    ///
+    /// Pairs keep their order. `None` if the content type doesn't match or there's
+    /// no body.
+    pub fn form_body(&self) -> Option<Vec<(String, String)>> {
+        let content_type = self.content_type()?;
+
+        if content_type.essence != "application/x-www-form-urlencoded" {
+            return None;
+        }
+
+        let body = self.body.as_deref()?;
+
+        Some(
+            form_urlencoded::parse(body.trim_end().as_bytes())
+                .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                .collect(),
+        )
+    }
+
+    /// Check if the connection should persist based on the version and `Connection` header
+    pub fn should_keep_alive(&self) -> bool {
+        should_keep_alive(&self.http_version, &self.headers)
+    }
+
+    /// Check if the request carries a message body based on its method and framing headers
+    ///
+    /// `HEAD` requests never have a body. Otherwise a body is signaled by a
+    /// `Transfer-Encoding` header or a non-zero `Content-Length`.
+    pub fn has_message_body(&self) -> bool {
+        if self.method == HttpMethod::HEAD {
+            return false;
+        }
+
+        if find_header(&self.headers, "Transfer-Encoding").is_some() {
+            return true;
+        }
+
+        matches!(self.header_as::<u64>("Content-Length"), Some(Ok(length)) if length > 0)
+    }
+
+    /// Get the lowercased transfer codings in the order they were applied
+    ///
+    /// Values from every `Transfer-Encoding` header are split on `,` and trimmed.
+    pub fn transfer_encodings(&self) -> Vec<String> {
+        filter_headers(&self.headers, "Transfer-Encoding")
+            .flat_map(|header| header.value().split(','))
+            .map(|coding| coding.trim().to_ascii_lowercase())
+            .filter(|coding| !coding.is_empty())
+            .collect()
+    }
+
+    /// Check if the final transfer coding is `chunked`
+    pub fn is_chunked(&self) -> bool {
+        self.transfer_encodings()
+            .last()
+            .is_some_and(|coding| coding == "chunked")
+    }
+
+    /// Remove hop-by-hop headers before forwarding the request
+    ///
+    /// This removes [crate::models::HOP_BY_HOP_HEADERS] and any headers named in a
+    /// `Connection` header value.
+    pub fn strip_hop_by_hop(&mut self) {
+        strip_hop_by_hop(&mut self.headers);
+    }
+
+    /// Check if the request is a WebSocket opening handshake
+    ///
+    /// This requires a `GET` with version 1.1 or later, `Upgrade: websocket`,
+    /// `Connection: Upgrade`, and a `Sec-WebSocket-Key` header.
+    pub fn is_websocket_upgrade(&self) -> bool {
+        self.method == HttpMethod::GET
+            && self
+                .http_version
+                .major_minor()
+                .is_some_and(|version| version >= (1, 1))
+            && filter_headers(&self.headers, "Upgrade").any(|header| header.has_token("websocket"))
+            && connection_has_token(&self.headers, "Upgrade")
+            && find_header(&self.headers, "Sec-WebSocket-Key").is_some()
+    }
+
+    /// Check if an `Expect` header asks for an interim `100 Continue` response
+    pub fn expects_continue(&self) -> bool {
+        filter_headers(&self.headers, "Expect").any(|header| header.has_token("100-continue"))
+    }
+
+    /// Get a copy of the request with the values of headers matching keys replaced
+    ///
+    /// Keys are matched ignoring ASCII case.
+    pub fn redacted(&self, keys: &[&str]) -> HttpRequest {
+        let mut request = self.clone();
+
+        for header in request.headers.iter_mut() {
+            if keys.iter().any(|key| header.has_key(key)) {
+                let redacted = HttpHeader::new(header.key(), REDACTED_VALUE);
+                *header = redacted;
+            }
+        }
+
+        request
+    }
+
+    /// Get a copy of the request with [SENSITIVE_HEADERS] redacted
+    pub fn redacted_default(&self) -> HttpRequest {
+        self.redacted(&SENSITIVE_HEADERS)
+    }
+
+    /// Get header patterns that could be used for request smuggling
+    pub fn smuggling_risks(&self) -> Vec<SmugglingRisk> {
+        find_smuggling_risks(&self.headers)
+    }
+
+    /// Get a normalized message for stable snapshots
+    ///
+    /// The method is uppercased, a missing version becomes `HTTP/1.1`, and header keys
+    /// are canonically cased then sorted, keeping duplicates in their original order.
+    pub fn to_canonical_string(&self) -> String {
+        let mut request = self.clone();
+
+        request.method = self.method.as_str().to_ascii_uppercase().as_str().into();
+
+        if self.http_version.is_empty() {
+            request.http_version = HttpVersion::default();
+        }
+
+        request.headers = self
+            .headers
+            .iter()
+            .map(|header| HttpHeader::new(&header.canonical_key(), header.value()))
+            .collect();
+        request.headers.sort_by(|a, b| a.key().cmp(b.key()));
+
+        request.to_string()
+    }
+
+    /// Get the structural differences between this request and another
+    ///
+    /// Headers are compared by key ignoring case and order.
+    pub fn diff(&self, other: &HttpRequest) -> Vec<RequestDiff> {
+        let mut diffs = vec![];
+
+        if self.method != other.method {
+            diffs.push(RequestDiff::MethodChanged);
+        }
+
+        if self.uri != other.uri {
+            diffs.push(RequestDiff::UriChanged);
+        }
+
+        if self.http_version != other.http_version {
+            diffs.push(RequestDiff::VersionChanged);
+        }
+
+        diffs.extend(diff_headers(&self.headers, &other.headers));
+
+        if self.body != other.body {
+            diffs.push(RequestDiff::BodyChanged);
+        }
+
+        diffs
+    }
+}
+
+impl fmt::Display for HttpRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}\r\n", self.method, self.uri, self.http_version)?;
+
+        for header in &self.headers {
+            write!(f, "{header}\r\n")?;
+        }
+
+        write!(f, "\r\n")?;
+
+        if let Some(body) = &self.body {
+            write!(f, "{body}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<HttpRequest> for String {
+    fn from(value: HttpRequest) -> Self {
+        value.to_string()
+    }
+}
+
+/// Compares the wire serialization to the string with `\r\n` normalized to `\n`
+///
+/// Line endings in the body are normalized too, so comparisons aren't sensitive to
+/// CRLF vs LF terminators.
+impl PartialEq<str> for HttpRequest {
+    fn eq(&self, other: &str) -> bool {
+        self.to_string().replace("\r\n", "\n") == other.replace("\r\n", "\n")
+    }
+}
+
+/// See [HttpRequest]'s `PartialEq<str>`
+impl PartialEq<&str> for HttpRequest {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
 }
 
 impl HttpBody for HttpRequest {
@@ -111,36 +648,52 @@ impl<'a> TryFrom<PartialHttpRequest<'a>> for HttpRequest {
             .ok_or(Error::missing_required("http_version"))?;
 
         Ok(Self {
-            uri: uri.into(),
+            uri: Uri::parse(uri)?,
             method: method.into(),
             headers: value
                 .header_strs()
                 .into_iter()
-                .map(|header| header.into())
-                .collect(),
+                .map(|header| HttpHeader::from_with_sep(header, value.header_separator()))
+                .collect::<Result<Vec<HttpHeader>, Error>>()?,
             body: value.body_str().map(|body| body.to_string()),
             http_version: http_version.into(),
         })
     }
 }
 
-impl<'a> From<ParsedHttpRequest<'a>> for HttpRequest {
-    fn from(value: ParsedHttpRequest) -> Self {
-        Self {
-            uri: value.uri_str().into(),
+impl<'a> TryFrom<ParsedHttpRequest<'a>> for HttpRequest {
+    type Error = Error;
+
+    fn try_from(value: ParsedHttpRequest<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uri: Uri::parse(value.uri_str())?,
             method: value.method_str().into(),
             headers: value
                 .header_strs()
                 .into_iter()
-                .map(|header| header.into())
-                .collect(),
+                .map(parse_folded_header)
+                .collect::<Result<Vec<HttpHeader>, Error>>()?,
             body: value.body_str().map(|body| body.to_string()),
             http_version: value.http_version_str().into(),
-        }
+        })
     }
 }
 
+/// Hash parts with 64-bit FNV-1a, ending each part with a zero byte so they can't run together
+fn fnv1a(parts: &[String]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    parts
+        .iter()
+        .flat_map(|part| part.bytes().chain([0]))
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+}
+
 #[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
 mod from_partial_request_tests {
     use crate::{
         error::Error,
@@ -181,7 +734,8 @@ mod from_partial_request_tests {
 #[cfg(test)]
 mod request_tests {
     use crate::models::{
-        body::HttpBody,
+        ByteRange, ParsedHttpRequest, RequestDiff, StandardHeader,
+        body::{BodyState, HttpBody},
         headers::HttpHeader,
         request::{HttpMethod, HttpRequest},
     };
@@ -210,6 +764,143 @@ mod request_tests {
         assert_eq!(&expected_headers_in_order, request.headers())
     }
 
+    #[test]
+    fn test_request_set_headers() {
+        let mut request = HttpRequest::get("https://example.com", vec!["Accept: */*".into()]);
+
+        request.set_headers(vec!["Z-Last: 1".into(), "A-First: 2".into()]);
+
+        assert_eq!(request.header_keys(), vec!["Z-Last", "A-First"]);
+
+        request.clear_headers();
+
+        assert!(request.headers().is_empty());
+    }
+
+    #[test]
+    fn test_request_merge_headers_from() {
+        let defaults = HttpRequest::get(
+            "https://example.com",
+            vec![
+                "accept: application/json".into(),
+                "X-Tag: a".into(),
+                "X-Tag: b".into(),
+            ],
+        );
+
+        let mut request = HttpRequest::get(
+            "https://example.com",
+            vec!["Accept: */*".into(), "x-key: 1".into()],
+        );
+        request.merge_headers_from(&defaults, false);
+
+        assert_eq!(
+            request.headers,
+            vec![
+                HttpHeader::new("Accept", "*/*"),
+                HttpHeader::new("x-key", "1"),
+                HttpHeader::new("X-Tag", "a"),
+                HttpHeader::new("X-Tag", "b"),
+            ]
+        );
+
+        let mut request = HttpRequest::get(
+            "https://example.com",
+            vec!["Accept: */*".into(), "x-key: 1".into(), "x-tag: c".into()],
+        );
+        request.merge_headers_from(&defaults, true);
+
+        assert_eq!(
+            request.headers,
+            vec![
+                HttpHeader::new("x-key", "1"),
+                HttpHeader::new("accept", "application/json"),
+                HttpHeader::new("X-Tag", "a"),
+                HttpHeader::new("X-Tag", "b"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_request_header_keys() {
+        let request = HttpRequest::get(
+            "https://example.com",
+            vec!["Accept: */*".into(), "X-Key: 1".into(), "x-key: 2".into()],
+        );
+
+        assert_eq!(request.header_keys(), vec!["Accept", "X-Key", "x-key"]);
+    }
+
+    #[test]
+    fn test_request_headers_in_order() {
+        let mut request: HttpRequest = ParsedHttpRequest::parse(
+            "GET https://example.com/ HTTP/1.1\nZ-Last: 1\nA-First: 2\nM-Middle: 3\n\n",
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+        request.set_header("A-First", "updated");
+        request.set_header("B-New", "4");
+
+        let keys: Vec<&str> = request
+            .headers_in_order()
+            .iter()
+            .map(|header| header.key())
+            .collect();
+
+        assert_eq!(keys, vec!["Z-Last", "A-First", "M-Middle", "B-New"]);
+
+        assert_eq!(
+            request.to_string(),
+            "GET https://example.com/ HTTP/1.1\r\n\
+             Z-Last: 1\r\n\
+             A-First: updated\r\n\
+             M-Middle: 3\r\n\
+             B-New: 4\r\n\
+             \r\n"
+        );
+    }
+
+    #[test]
+    fn test_request_minimal() {
+        let request = HttpRequest::minimal(HttpMethod::DELETE, "https://example.com:8080/users/1");
+
+        assert_eq!(request.method, HttpMethod::DELETE);
+        assert_eq!(request.http_version, "HTTP/1.1".into());
+        assert_eq!(
+            &vec![HttpHeader::new("Host", "example.com:8080")],
+            request.headers()
+        );
+        assert!(request.body.is_none());
+
+        let request = HttpRequest::minimal(HttpMethod::GET, "example.com/path");
+
+        assert_eq!(
+            &vec![HttpHeader::new("Host", "example.com")],
+            request.headers()
+        );
+    }
+
+    #[test]
+    fn test_request_with_builders() {
+        let request = HttpRequest::get("https://example.com", vec!["Accept: */*".into()])
+            .with_method(HttpMethod::PUT)
+            .with_header("Content-Type", "text/plain")
+            .with_header("Accept", "text/plain")
+            .with_body("body");
+
+        assert_eq!(request.method, HttpMethod::PUT);
+        assert_eq!(
+            &vec![
+                HttpHeader::new("Accept", "text/plain"),
+                HttpHeader::new("Content-Type", "text/plain")
+            ],
+            request.headers()
+        );
+        assert_eq!(request.get_body(), &Some("body".to_string()));
+    }
+
     #[test]
     fn test_request_get() {
         let request = HttpRequest::get(
@@ -240,4 +931,641 @@ mod request_tests {
 
         assert_eq!(expected_headers_in_order, *request.headers())
     }
+
+    #[test]
+    fn test_request_append_body() {
+        let mut request = HttpRequest::post("https://example.com", vec![], None);
+
+        request.append_body("{\"id\":");
+        request.append_body(" 100}");
+
+        assert_eq!(request.get_body(), &Some("{\"id\": 100}".to_string()));
+    }
+
+    #[test]
+    fn test_request_fold_all_headers() {
+        let mut request = HttpRequest::get(
+            "https://example.com",
+            vec![
+                "Accept: a".into(),
+                "Set-Cookie: a=1".into(),
+                "Cache-Control: no-cache".into(),
+                "accept: b".into(),
+                "Set-Cookie: b=2".into(),
+                "Cache-Control: no-store".into(),
+            ],
+        );
+
+        request.fold_all_headers();
+
+        assert_eq!(
+            &vec![
+                HttpHeader::new("Accept", "a, b"),
+                HttpHeader::new("Set-Cookie", "a=1"),
+                HttpHeader::new("Cache-Control", "no-cache, no-store"),
+                HttpHeader::new("Set-Cookie", "b=2"),
+            ],
+            request.headers()
+        );
+    }
+
+    #[test]
+    fn test_request_rename_header() {
+        let mut request = HttpRequest::get(
+            "https://example.com",
+            vec![
+                "X-A: 1".into(),
+                "x-api-key: 123".into(),
+                "X-Api-Key: 456".into(),
+            ],
+        );
+
+        assert!(request.rename_header("X-API-KEY", "Authorization"));
+        assert!(!request.rename_header("X-Missing", "X-Other"));
+
+        assert_eq!(
+            &vec![
+                HttpHeader::new("X-A", "1"),
+                HttpHeader::new("Authorization", "123"),
+                HttpHeader::new("X-Api-Key", "456")
+            ],
+            request.headers()
+        );
+    }
+
+    #[test]
+    fn test_request_get_standard_header() {
+        let request = HttpRequest::get(
+            "https://example.com",
+            vec!["content-type: text/plain".into()],
+        );
+
+        assert_eq!(
+            request.get_standard_header(StandardHeader::ContentType),
+            Some(&"content-type: text/plain".into())
+        );
+        assert_eq!(request.get_standard_header(StandardHeader::Host), None);
+    }
+
+    #[test]
+    fn test_request_header_as() {
+        let request = HttpRequest::post(
+            "https://example.com",
+            vec!["content-length: 42".into(), "X-Count: many".into()],
+            None,
+        );
+
+        assert_eq!(request.header_as::<u64>("Content-Length"), Some(Ok(42)));
+        assert!(matches!(request.header_as::<u64>("X-Count"), Some(Err(_))));
+        assert_eq!(request.header_as::<u64>("X-Missing"), None);
+    }
+
+    #[test]
+    fn test_request_query_map() {
+        let request = HttpRequest::get(
+            "https://example.com/path?tag=a&tag=b&na%20me=v%26al&empty",
+            vec![],
+        );
+
+        let query_map = request.query_map();
+
+        assert_eq!(query_map.len(), 3);
+        assert_eq!(query_map["tag"], vec!["a", "b"]);
+        assert_eq!(query_map["na me"], vec!["v&al"]);
+        assert_eq!(query_map["empty"], vec![""]);
+
+        let request = HttpRequest::get("https://example.com/path", vec![]);
+
+        assert!(request.query_map().is_empty());
+    }
+
+    #[test]
+    fn test_request_target() {
+        let request = HttpRequest::get("https://example.com/path?q=1&r#frag", vec![]);
+        assert_eq!(request.request_target(), "https://example.com/path?q=1&r");
+
+        let request = HttpRequest::get("/path?q=1&r", vec![]);
+        assert_eq!(request.request_target(), "/path?q=1&r");
+
+        let request = HttpRequest::get("example.com", vec![]);
+        assert_eq!(request.request_target(), "/");
+
+        let request = HttpRequest::minimal("CONNECT".into(), "https://example.com:8443");
+        assert_eq!(request.request_target(), "example.com:8443");
+
+        let request = HttpRequest::minimal("CONNECT".into(), "example.com:443");
+        assert_eq!(request.request_target(), "example.com:443");
+
+        let request = HttpRequest::get("HTTP://example.com/a", vec![]);
+        assert_eq!(request.request_target(), "http://example.com/a");
+
+        let request = HttpRequest::get("example.com/r?u=http://x", vec![]);
+        assert_eq!(request.request_target(), "/r?u=http://x");
+
+        let request: HttpRequest = ParsedHttpRequest::parse("OPTIONS * HTTP/1.1\n\n")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(request.request_target(), "*");
+    }
+
+    #[test]
+    fn test_request_cache_key() {
+        let request = HttpRequest::get(
+            "https://EXAMPLE.com:8443/path?b=2&a=1",
+            vec!["Accept: text/html".into(), "accept-language: en".into()],
+        );
+
+        let key = request.cache_key(&["Accept-Language", "accept", "X-Missing"]);
+
+        assert_eq!(key.len(), 16);
+        assert!(key.bytes().all(|byte| byte.is_ascii_hexdigit()));
+        assert_eq!(
+            key,
+            request.cache_key(&["x-missing", "ACCEPT", "accept-language"])
+        );
+        assert_ne!(key, request.cache_key(&["Accept", "Accept-Language"]));
+        assert_ne!(
+            request.cache_key(&["Accept"]),
+            HttpRequest::get(
+                "https://example.com:8443/path?a=1&b=2",
+                vec!["Accept: text/plain".into()]
+            )
+            .cache_key(&["Accept"])
+        );
+        assert_eq!(
+            HttpRequest::get("https://example.com", vec![]).cache_key(&[]),
+            "14eda9c93d3f592c"
+        );
+
+        let reordered = HttpRequest::get(
+            "https://example.com:8443/path?a=1&b=2",
+            vec!["Accept-Language: en".into(), "Accept: text/html".into()],
+        );
+
+        assert_eq!(
+            request.cache_key(&["Accept", "Accept-Language"]),
+            reordered.cache_key(&["Accept-Language", "Accept"])
+        );
+        assert_ne!(
+            request.cache_key(&[]),
+            request.clone().with_method(HttpMethod::HEAD).cache_key(&[])
+        );
+    }
+
+    #[test]
+    fn test_request_ranges() {
+        let request = HttpRequest::get("https://example.com", vec!["range: bytes=0-499".into()]);
+
+        assert_eq!(
+            request.ranges(),
+            Some(vec![ByteRange {
+                start: Some(0),
+                end: Some(499)
+            }])
+        );
+
+        let request = HttpRequest::get("https://example.com", vec!["Range: bytes=x".into()]);
+        assert_eq!(request.ranges(), Some(vec![]));
+
+        let request = HttpRequest::get("https://example.com", vec![]);
+        assert_eq!(request.ranges(), None);
+    }
+
+    #[test]
+    fn test_request_forwarded() {
+        let request = HttpRequest::get(
+            "https://example.com",
+            vec![
+                "X-Forwarded-For: 203.0.113.195, 70.41.3.18".into(),
+                "x-forwarded-for: 150.172.238.178".into(),
+                "Forwarded: for=192.0.2.43, for=198.51.100.17;proto=https".into(),
+            ],
+        );
+
+        assert_eq!(
+            request.forwarded_for(),
+            vec!["203.0.113.195", "70.41.3.18", "150.172.238.178"]
+        );
+
+        let forwarded = request.forwarded();
+
+        assert_eq!(forwarded.len(), 2);
+        assert_eq!(forwarded[0].for_.as_deref(), Some("192.0.2.43"));
+        assert_eq!(forwarded[1].proto.as_deref(), Some("https"));
+
+        let request = HttpRequest::get("https://example.com", vec![]);
+
+        assert!(request.forwarded_for().is_empty());
+        assert!(request.forwarded().is_empty());
+    }
+
+    #[test]
+    fn test_request_body_lines() {
+        let request = HttpRequest::post(
+            "https://example.com",
+            vec![],
+            Some("{\"a\":1}\r\n\n{\"b\":2}".to_string()),
+        );
+
+        assert_eq!(request.body_lines(), vec![r#"{"a":1}"#, "", r#"{"b":2}"#]);
+
+        let request = request.with_body("line\n");
+        assert_eq!(request.body_lines(), vec!["line"]);
+
+        let request = HttpRequest::get("https://example.com", vec![]);
+        assert!(request.body_lines().is_empty());
+    }
+
+    #[test]
+    fn test_request_form_body() {
+        let request = HttpRequest::post(
+            "https://example.com",
+            vec!["Content-Type: Application/X-WWW-Form-Urlencoded; charset=utf-8".into()],
+            Some("name=Jane+Doe&tag=a%26b&tag=c&flag\n".to_string()),
+        );
+
+        assert_eq!(
+            request.form_body(),
+            Some(vec![
+                ("name".to_string(), "Jane Doe".to_string()),
+                ("tag".to_string(), "a&b".to_string()),
+                ("tag".to_string(), "c".to_string()),
+                ("flag".to_string(), String::new()),
+            ])
+        );
+
+        let request = HttpRequest::post(
+            "https://example.com",
+            vec!["Content-Type: application/json".into()],
+            Some("a=1".to_string()),
+        );
+        assert_eq!(request.form_body(), None);
+
+        let request = HttpRequest::post(
+            "https://example.com",
+            vec!["Content-Type: application/x-www-form-urlencoded".into()],
+            None,
+        );
+        assert_eq!(request.form_body(), None);
+    }
+
+    #[test]
+    fn test_request_body_state() {
+        let mut request = HttpRequest::get("https://example.com", vec![]);
+        assert_eq!(request.body_state(), BodyState::Absent);
+
+        request.set_body(Some(String::new()));
+        assert_eq!(request.body_state(), BodyState::Empty);
+
+        request.append_body("{}");
+        assert_eq!(request.body_state(), BodyState::Present);
+    }
+
+    #[test]
+    fn test_request_body_preview() {
+        let request = HttpRequest::post("https://example.com", vec![], Some("héllo".to_string()));
+
+        assert_eq!(request.body_preview(6), Some("héllo".to_string()));
+        assert_eq!(request.body_preview(3), Some("hé…".to_string()));
+        assert_eq!(request.body_preview(2), Some("h…".to_string()));
+        assert_eq!(request.body_preview(0), Some("…".to_string()));
+
+        let request = HttpRequest::get("https://example.com", vec![]);
+        assert_eq!(request.body_preview(10), None);
+    }
+
+    #[test]
+    fn test_request_accept() {
+        let request = HttpRequest::get(
+            "https://example.com",
+            vec!["Accept: */*;q=0.1".into(), "accept: Text/HTML".into()],
+        );
+
+        let media_types: Vec<String> = request
+            .accept()
+            .into_iter()
+            .map(|range| range.media_type)
+            .collect();
+
+        assert_eq!(media_types, vec!["text/html", "*/*"]);
+
+        let request = HttpRequest::get("https://example.com", vec![]);
+        assert!(request.accept().is_empty());
+    }
+
+    #[test]
+    fn test_request_bearer_token() {
+        let request = HttpRequest::get(
+            "https://example.com",
+            vec!["authorization: bearer   abc.def  ".into()],
+        );
+        assert_eq!(request.bearer_token(), Some("abc.def"));
+
+        for headers in [
+            vec![],
+            vec!["Authorization: Basic dXNlcjpwYXNz".into()],
+            vec!["Authorization: Bearer".into()],
+            vec!["Authorization: Bearerabc".into()],
+        ] {
+            assert_eq!(
+                HttpRequest::get("https://example.com", headers).bearer_token(),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_request_content_type() {
+        let request = HttpRequest::post(
+            "https://example.com",
+            vec!["content-type: application/json; charset=utf-8".into()],
+            None,
+        );
+
+        let media_type = request.content_type().unwrap();
+
+        assert_eq!(media_type.essence, "application/json");
+        assert_eq!(media_type.charset, Some("utf-8".to_string()));
+    }
+
+    #[test]
+    fn test_request_should_keep_alive() {
+        let mut request = HttpRequest::get("https://example.com", vec![]);
+        assert!(request.should_keep_alive());
+
+        request.set_header("Connection", "close");
+        assert!(!request.should_keep_alive());
+
+        request.http_version = "HTTP/1.0".into();
+        request.set_header("Connection", "keep-alive");
+        assert!(request.should_keep_alive());
+    }
+
+    #[test]
+    fn test_request_transfer_encodings() {
+        let request = HttpRequest::get(
+            "https://example.com",
+            vec![
+                "Transfer-Encoding: GZIP, ,chunked".into(),
+                "transfer-encoding: identity".into(),
+            ],
+        );
+
+        assert_eq!(
+            request.transfer_encodings(),
+            vec!["gzip", "chunked", "identity"]
+        );
+        assert!(!request.is_chunked());
+
+        let request = HttpRequest::get(
+            "https://example.com",
+            vec!["Transfer-Encoding: gzip, Chunked".into()],
+        );
+        assert!(request.is_chunked());
+
+        let request = HttpRequest::get("https://example.com", vec![]);
+        assert!(request.transfer_encodings().is_empty());
+        assert!(!request.is_chunked());
+    }
+
+    #[test]
+    fn test_request_has_message_body() {
+        let mut request = HttpRequest::post(
+            "https://example.com",
+            vec!["Content-Length: 4".into()],
+            Some("body".to_string()),
+        );
+        assert!(request.has_message_body());
+
+        request.method = HttpMethod::HEAD;
+        assert!(!request.has_message_body());
+
+        let mut request = HttpRequest::post("https://example.com", vec![], None);
+        assert!(!request.has_message_body());
+
+        request.set_header("Content-Length", "0");
+        assert!(!request.has_message_body());
+
+        request.set_header("Transfer-Encoding", "chunked");
+        assert!(request.has_message_body());
+    }
+
+    #[test]
+    fn test_request_strip_hop_by_hop() {
+        let mut request = HttpRequest::get(
+            "https://example.com",
+            vec![
+                "Connection: close, X-Hop".into(),
+                "X-Hop: 1".into(),
+                "Accept: */*".into(),
+            ],
+        );
+
+        request.strip_hop_by_hop();
+
+        assert_eq!(&vec![HttpHeader::new("Accept", "*/*")], request.headers());
+    }
+
+    #[test]
+    fn test_request_is_websocket_upgrade() {
+        let mut request = HttpRequest::get(
+            "https://example.com/chat",
+            vec![
+                "upgrade: WebSocket".into(),
+                "Connection: keep-alive, Upgrade".into(),
+                "Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==".into(),
+            ],
+        );
+        assert!(request.is_websocket_upgrade());
+
+        request.http_version = "HTTP/1.0".into();
+        assert!(!request.is_websocket_upgrade());
+
+        request.http_version = "HTTP/1.1".into();
+        request.method = HttpMethod::POST;
+        assert!(!request.is_websocket_upgrade());
+
+        request.method = HttpMethod::GET;
+        request.set_header("Connection", "keep-alive");
+        assert!(!request.is_websocket_upgrade());
+    }
+
+    #[test]
+    fn test_request_expects_continue() {
+        let mut request = HttpRequest::post("https://example.com", vec![], None);
+        assert!(!request.expects_continue());
+
+        request.set_header("expect", "100-Continue");
+        assert!(request.expects_continue());
+
+        request.set_header("expect", "200-ok");
+        assert!(!request.expects_continue());
+    }
+
+    #[test]
+    fn test_request_redacted() {
+        let request = HttpRequest::get(
+            "https://example.com",
+            vec!["x-api-key: abc123".into(), "Accept: */*".into()],
+        );
+
+        let redacted = request.redacted(&["X-API-KEY"]);
+
+        assert_eq!(
+            &vec![
+                HttpHeader::new("x-api-key", "***"),
+                HttpHeader::new("Accept", "*/*")
+            ],
+            redacted.headers()
+        );
+        assert_eq!(request.get_header("x-api-key").unwrap().value(), "abc123");
+    }
+
+    #[test]
+    fn test_request_redacted_default() {
+        let request = HttpRequest::get(
+            "https://example.com",
+            vec![
+                "authorization: Bearer token".into(),
+                "Cookie: session=1".into(),
+                "Accept: */*".into(),
+            ],
+        );
+
+        let redacted = request.redacted_default();
+
+        assert_eq!(
+            &vec![
+                HttpHeader::new("authorization", "***"),
+                HttpHeader::new("Cookie", "***"),
+                HttpHeader::new("Accept", "*/*")
+            ],
+            redacted.headers()
+        );
+    }
+
+    #[test]
+    fn test_request_diff_equal() {
+        let left = HttpRequest::get("https://example.com", vec!["A: 1".into(), "B: 2".into()]);
+        let right = HttpRequest::get("https://example.com", vec!["b: 2".into(), "a: 1".into()]);
+
+        assert_eq!(left.diff(&right), vec![]);
+    }
+
+    #[test]
+    fn test_request_diff_changes() {
+        let left = HttpRequest::get("https://example.com", vec!["A: 1".into()]);
+        let mut right = HttpRequest::post(
+            "https://example.com/other",
+            vec!["A: 2".into()],
+            Some("body".to_string()),
+        );
+        right.http_version = "HTTP/2".into();
+
+        assert_eq!(
+            left.diff(&right),
+            vec![
+                RequestDiff::MethodChanged,
+                RequestDiff::UriChanged,
+                RequestDiff::VersionChanged,
+                RequestDiff::HeaderChanged("A".to_string()),
+                RequestDiff::BodyChanged,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_request_display() {
+        let request = HttpRequest::post(
+            "https://example.com/path",
+            vec!["x-key: 123".into()],
+            Some("body".to_string()),
+        );
+
+        assert_eq!(
+            request.to_string(),
+            "POST https://example.com/path HTTP/1.1\r\nx-key: 123\r\n\r\nbody"
+        );
+    }
+
+    #[test]
+    fn test_request_eq_str() {
+        let request = HttpRequest::post(
+            "https://example.com/path",
+            vec!["x-key: 123".into()],
+            Some("a\r\nb".to_string()),
+        );
+
+        assert_eq!(
+            request,
+            "POST https://example.com/path HTTP/1.1\r\nx-key: 123\r\n\r\na\r\nb"
+        );
+        assert_eq!(
+            request,
+            "POST https://example.com/path HTTP/1.1\nx-key: 123\n\na\nb"
+        );
+        assert_ne!(
+            request,
+            "POST https://example.com/path HTTP/1.1\nx-key: 123\n\na b"
+        );
+        assert!(
+            HttpRequest::get("https://example.com/", vec![])
+                == *"GET https://example.com/ HTTP/1.1\n\n"
+        );
+    }
+
+    #[test]
+    fn test_request_into_string() {
+        let request = HttpRequest::get("https://example.com/path", vec![]);
+
+        assert_eq!(
+            String::from(request),
+            "GET https://example.com/path HTTP/1.1\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn test_request_to_canonical_string() {
+        let mut request = HttpRequest::get(
+            "https://example.com/path",
+            vec![
+                "x-key: 2".into(),
+                "accept: */*".into(),
+                "X-KEY: 1".into(),
+                "content-TYPE: text/plain".into(),
+            ],
+        );
+        request.method = "get".into();
+        request.http_version = "".into();
+
+        assert_eq!(
+            request.to_canonical_string(),
+            "GET https://example.com/path HTTP/1.1\r\n\
+             Accept: */*\r\n\
+             Content-Type: text/plain\r\n\
+             X-Key: 2\r\n\
+             X-Key: 1\r\n\
+             \r\n"
+        );
+    }
+
+    #[test]
+    fn test_equal_requests_to_canonical_string() {
+        let request = HttpRequest::minimal(HttpMethod::GET, "Example.com");
+        let normalized = HttpRequest::minimal(HttpMethod::GET, "https://example.com/");
+
+        assert_eq!(request, normalized);
+        assert_eq!(
+            request.to_canonical_string(),
+            normalized.to_canonical_string()
+        );
+    }
+
+    #[test]
+    fn test_request_content_type_missing() {
+        let request = HttpRequest::get("https://example.com", vec![]);
+
+        assert_eq!(request.content_type(), None);
+    }
 }
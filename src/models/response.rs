@@ -1,30 +1,90 @@
 use core::fmt;
+use std::str::FromStr;
 
+use crate::error::Error;
 use crate::models::{
+    HttpMethod, HttpVersion, MediaType, Uri,
     body::{HttpBody, PossibleHttpBody},
-    headers::HttpHeader,
+    connection::should_keep_alive,
+    headers::{HttpHeader, find_header},
 };
 
 #[derive(Debug, Clone)]
 pub struct HttpResponse {
+    pub http_version: HttpVersion,
     pub status_code: HttpStatusCode,
+    /// The reason phrase as stored, if it was defined
+    pub reason: Option<String>,
     pub headers: Vec<HttpHeader>,
     pub body: PossibleHttpBody,
 }
 
 impl HttpResponse {
+    /// Start building a `HTTP/1.1 200` response with the canonical reason phrase
+    pub fn builder() -> HttpResponseBuilder {
+        HttpResponseBuilder(HttpResponse::default())
+    }
+
+    /// Create a `HTTP/1.1` response with the canonical reason phrase
     pub fn new(status_code: HttpStatusCode, headers: Vec<HttpHeader>, body: Option<&str>) -> Self {
+        Self::from_parts(
+            Default::default(),
+            status_code,
+            None,
+            headers,
+            body.map(|b| b.to_string()),
+        )
+    }
+
+    /// Create a response from its status line components, headers, and body
+    pub fn from_parts(
+        http_version: HttpVersion,
+        status_code: HttpStatusCode,
+        reason: Option<String>,
+        headers: Vec<HttpHeader>,
+        body: PossibleHttpBody,
+    ) -> Self {
         Self {
+            http_version,
             status_code,
+            reason,
             headers,
-            body: body.map(|b| b.to_string()),
+            body,
         }
     }
 
+    /// Get the stored reason phrase, falling back to the status code's canonical phrase
+    ///
+    /// This is empty for unknown status codes without a stored reason.
+    pub fn reason(&self) -> &str {
+        self.reason
+            .as_deref()
+            .or(self.status_code.canonical_reason())
+            .unwrap_or_default()
+    }
+
     pub fn headers(&self) -> &Vec<HttpHeader> {
         &self.headers
     }
 
+    /// Get each header key in order, including duplicates
+    pub fn header_keys(&self) -> Vec<&str> {
+        self.headers.iter().map(|header| header.key()).collect()
+    }
+
+    /// Get the `Location` header as an absolute uri or relative reference
+    ///
+    /// An absent, empty, or malformed `Location` is `None`. See [Uri::is_relative].
+    pub fn location(&self) -> Option<Uri> {
+        let location = find_header(&self.headers, "Location")?.value().trim();
+
+        if location.is_empty() {
+            return None;
+        }
+
+        Uri::parse_reference(location).ok()
+    }
+
     pub fn get_header(&self, key: &str) -> Option<&HttpHeader> {
         self.headers.iter().find(|header| header.key() == key)
     }
@@ -38,9 +98,124 @@ impl HttpResponse {
         }
     }
 
+    /// Replace all headers, keeping the order given
+    pub fn set_headers(&mut self, headers: Vec<HttpHeader>) {
+        self.headers = headers;
+    }
+
+    /// Remove all headers
+    pub fn clear_headers(&mut self) {
+        self.headers.clear();
+    }
+
     pub fn get_header_mut(&mut self, key: &str) -> Option<&mut HttpHeader> {
         self.headers.iter_mut().find(|header| header.key() == key)
     }
+
+    /// Get the parsed media type from the `Content-Type` header, if defined
+    pub fn content_type(&self) -> Option<MediaType> {
+        find_header(&self.headers, "Content-Type")
+            .and_then(|header| MediaType::parse(header.value()))
+    }
+
+    /// Check if the connection should persist based on the version and `Connection` header
+    pub fn should_keep_alive(&self) -> bool {
+        should_keep_alive(&self.http_version, &self.headers)
+    }
+
+    /// Check if the status code allows a body regardless of headers
+    ///
+    /// `1xx`, `204`, and `304` responses never have a body. Use
+    /// [HttpResponse::has_message_body] to also account for `HEAD` requests.
+    pub fn can_have_body(&self) -> bool {
+        !(self.status_code.is_informational()
+            || self.status_code.as_u16() == 204
+            || self.status_code.as_u16() == 304)
+    }
+
+    /// Check if the response carries a message body for a request with the method
+    ///
+    /// Responses to `HEAD` requests and `1xx`, `204`, and `304` responses never have a body.
+    pub fn has_message_body(&self, request_method: &HttpMethod) -> bool {
+        *request_method != HttpMethod::HEAD && self.can_have_body()
+    }
+}
+
+/// A builder for [HttpResponse] from [HttpResponse::builder]
+#[derive(Debug, Clone)]
+pub struct HttpResponseBuilder(HttpResponse);
+
+impl HttpResponseBuilder {
+    /// Set the status code
+    pub fn status(mut self, status_code: u16) -> Self {
+        self.0.status_code = status_code.into();
+        self
+    }
+
+    /// Set the reason phrase instead of the status code's canonical phrase
+    pub fn reason(mut self, reason: &str) -> Self {
+        self.0.reason = Some(reason.to_string());
+        self
+    }
+
+    /// Set the http version, e.g. `HTTP/1.0`
+    pub fn version(mut self, version: &str) -> Self {
+        self.0.http_version = version.into();
+        self
+    }
+
+    /// Add a header, keeping any existing headers with the same key
+    pub fn header(mut self, key: &str, value: &str) -> Self {
+        self.0.headers.push(HttpHeader::new(key, value));
+        self
+    }
+
+    /// Set the body
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.0.body = Some(body.into());
+        self
+    }
+
+    pub fn build(self) -> HttpResponse {
+        self.0
+    }
+}
+
+/// A `HTTP/1.1 200 OK` response without headers or a body
+impl Default for HttpResponse {
+    fn default() -> Self {
+        Self::new(200.into(), vec![], None)
+    }
+}
+
+impl fmt::Display for HttpResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}\r\n",
+            self.http_version,
+            self.status_code,
+            self.reason()
+        )?;
+
+        for header in &self.headers {
+            write!(f, "{header}\r\n")?;
+        }
+
+        write!(f, "\r\n")?;
+
+        if let Some(body) = &self.body {
+            write!(f, "{body}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<HttpResponse> for String {
+    fn from(value: HttpResponse) -> Self {
+        value.to_string()
+    }
 }
 
 impl HttpBody for HttpResponse {
@@ -53,13 +228,113 @@ impl HttpBody for HttpResponse {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct HttpStatusCode(u16);
 
 impl HttpStatusCode {
     pub fn new(status_code: u16) -> Self {
         Self(status_code)
     }
+
+    pub fn as_u16(&self) -> u16 {
+        self.0
+    }
+
+    /// Get the standard reason phrase for the status code, if known
+    pub fn canonical_reason(&self) -> Option<&'static str> {
+        let reason = match self.0 {
+            100 => "Continue",
+            101 => "Switching Protocols",
+            102 => "Processing",
+            103 => "Early Hints",
+            200 => "OK",
+            201 => "Created",
+            202 => "Accepted",
+            203 => "Non-Authoritative Information",
+            204 => "No Content",
+            205 => "Reset Content",
+            206 => "Partial Content",
+            207 => "Multi-Status",
+            208 => "Already Reported",
+            226 => "IM Used",
+            300 => "Multiple Choices",
+            301 => "Moved Permanently",
+            302 => "Found",
+            303 => "See Other",
+            304 => "Not Modified",
+            305 => "Use Proxy",
+            307 => "Temporary Redirect",
+            308 => "Permanent Redirect",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            402 => "Payment Required",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            406 => "Not Acceptable",
+            407 => "Proxy Authentication Required",
+            408 => "Request Timeout",
+            409 => "Conflict",
+            410 => "Gone",
+            411 => "Length Required",
+            412 => "Precondition Failed",
+            413 => "Content Too Large",
+            414 => "URI Too Long",
+            415 => "Unsupported Media Type",
+            416 => "Range Not Satisfiable",
+            417 => "Expectation Failed",
+            418 => "I'm a teapot",
+            421 => "Misdirected Request",
+            422 => "Unprocessable Content",
+            423 => "Locked",
+            424 => "Failed Dependency",
+            425 => "Too Early",
+            426 => "Upgrade Required",
+            428 => "Precondition Required",
+            429 => "Too Many Requests",
+            431 => "Request Header Fields Too Large",
+            451 => "Unavailable For Legal Reasons",
+            500 => "Internal Server Error",
+            501 => "Not Implemented",
+            502 => "Bad Gateway",
+            503 => "Service Unavailable",
+            504 => "Gateway Timeout",
+            505 => "HTTP Version Not Supported",
+            506 => "Variant Also Negotiates",
+            507 => "Insufficient Storage",
+            508 => "Loop Detected",
+            510 => "Not Extended",
+            511 => "Network Authentication Required",
+            _ => return None,
+        };
+
+        Some(reason)
+    }
+
+    /// Check if the status code is `1xx`
+    pub fn is_informational(&self) -> bool {
+        (100..200).contains(&self.0)
+    }
+
+    /// Check if the status code is `2xx`
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.0)
+    }
+
+    /// Check if the status code is `3xx`
+    pub fn is_redirection(&self) -> bool {
+        (300..400).contains(&self.0)
+    }
+
+    /// Check if the status code is `4xx`
+    pub fn is_client_error(&self) -> bool {
+        (400..500).contains(&self.0)
+    }
+
+    /// Check if the status code is `5xx`
+    pub fn is_server_error(&self) -> bool {
+        (500..600).contains(&self.0)
+    }
 }
 
 impl fmt::Display for HttpStatusCode {
@@ -68,6 +343,21 @@ impl fmt::Display for HttpStatusCode {
     }
 }
 
+/// Parses exactly three ASCII digits, e.g. `200`
+impl FromStr for HttpStatusCode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 3 || !s.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(Error::invalid_status_code(s));
+        }
+
+        s.parse()
+            .map(HttpStatusCode)
+            .map_err(|_| Error::invalid_status_code(s))
+    }
+}
+
 impl From<u16> for HttpStatusCode {
     fn from(value: u16) -> Self {
         HttpStatusCode(value)
@@ -78,6 +368,18 @@ impl From<u16> for HttpStatusCode {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_http_status_code_from_str() {
+        assert_eq!("204".parse(), Ok(HttpStatusCode::new(204)));
+
+        for status_code in ["+200", "20", "2000", "2o0", " 200", ""] {
+            assert_eq!(
+                status_code.parse::<HttpStatusCode>(),
+                Err(Error::invalid_status_code(status_code))
+            );
+        }
+    }
+
     #[test]
     fn test_http_status_code_new() {
         let status_code = HttpStatusCode::new(200);
@@ -96,6 +398,142 @@ mod tests {
         assert_eq!(status_code.0, 200);
     }
 
+    #[test]
+    fn test_http_status_code_ord() {
+        assert!(HttpStatusCode::from(404) < HttpStatusCode::from(500));
+
+        let mut status_codes: Vec<HttpStatusCode> = vec![500.into(), 200.into(), 404.into()];
+        status_codes.sort();
+
+        assert_eq!(status_codes, vec![200.into(), 404.into(), 500.into()]);
+
+        let counts: std::collections::BTreeMap<HttpStatusCode, usize> =
+            [(404.into(), 1), (200.into(), 2)].into_iter().collect();
+
+        assert_eq!(counts.keys().next(), Some(&200.into()));
+    }
+
+    #[test]
+    fn test_http_status_code_classification() {
+        assert!(HttpStatusCode::new(100).is_informational());
+        assert!(HttpStatusCode::new(204).is_success());
+        assert!(HttpStatusCode::new(304).is_redirection());
+        assert!(HttpStatusCode::new(404).is_client_error());
+        assert!(HttpStatusCode::new(503).is_server_error());
+        assert!(!HttpStatusCode::new(600).is_server_error());
+    }
+
+    #[test]
+    fn test_http_status_code_canonical_reason() {
+        assert_eq!(
+            HttpStatusCode::new(404).canonical_reason(),
+            Some("Not Found")
+        );
+        assert_eq!(HttpStatusCode::new(299).canonical_reason(), None);
+    }
+
+    #[test]
+    fn test_http_response_reason() {
+        let mut response = HttpResponse::new(200.into(), vec![], None);
+        assert_eq!(response.reason(), "OK");
+
+        response.reason = Some("Totally Fine".to_string());
+        assert_eq!(response.reason(), "Totally Fine");
+
+        let response = HttpResponse::new(299.into(), vec![], None);
+        assert_eq!(response.reason(), "");
+    }
+
+    #[test]
+    fn test_http_response_can_have_body() {
+        for status_code in [200, 404, 500] {
+            assert!(HttpResponse::new(status_code.into(), vec![], None).can_have_body());
+        }
+
+        for status_code in [100, 101, 204, 304] {
+            let response = HttpResponse::new(status_code.into(), vec![], Some("body"));
+            assert!(!response.can_have_body());
+        }
+    }
+
+    #[test]
+    fn test_http_response_has_message_body() {
+        let response = HttpResponse::new(200.into(), vec!["Content-Length: 4".into()], None);
+        assert!(response.has_message_body(&HttpMethod::GET));
+        assert!(!response.has_message_body(&HttpMethod::HEAD));
+
+        for status_code in [101, 204, 304] {
+            let response = HttpResponse::new(status_code.into(), vec![], None);
+            assert!(!response.has_message_body(&HttpMethod::GET));
+        }
+    }
+
+    #[test]
+    fn test_http_response_from_parts() {
+        let response = HttpResponse::from_parts(
+            "HTTP/1.0".into(),
+            404.into(),
+            Some("Gone Fishing".to_string()),
+            vec!["Content-Type: text/plain".into()],
+            Some("missing".to_string()),
+        );
+
+        assert_eq!(
+            response.to_string(),
+            "HTTP/1.0 404 Gone Fishing\r\nContent-Type: text/plain\r\n\r\nmissing"
+        );
+    }
+
+    #[test]
+    fn test_http_response_default() {
+        let response = HttpResponse::default();
+
+        assert_eq!(response.status_code, HttpStatusCode::new(200));
+        assert_eq!(response.reason(), "OK");
+        assert_eq!(response.http_version, HttpVersion::from("HTTP/1.1"));
+        assert!(response.headers.is_empty());
+        assert_eq!(response.body, None);
+    }
+
+    #[test]
+    fn test_http_response_builder() {
+        let response = HttpResponse::builder()
+            .status(404)
+            .header("Content-Type", "application/json")
+            .body(r#"{"error":"not found"}"#)
+            .build();
+
+        assert_eq!(
+            response.to_string(),
+            "HTTP/1.1 404 Not Found\r\n\
+             Content-Type: application/json\r\n\
+             \r\n\
+             {\"error\":\"not found\"}"
+        );
+
+        let response = HttpResponse::builder()
+            .version("HTTP/1.0")
+            .reason("Fine")
+            .build();
+
+        assert_eq!(response.to_string(), "HTTP/1.0 200 Fine\r\n\r\n");
+        assert_eq!(HttpResponse::builder().build().reason(), "OK");
+    }
+
+    #[test]
+    fn test_http_response_display() {
+        let response = HttpResponse::new(404.into(), vec!["x-key: 123".into()], Some("body"));
+
+        assert_eq!(
+            response.to_string(),
+            "HTTP/1.1 404 Not Found\r\nx-key: 123\r\n\r\nbody"
+        );
+
+        let response = HttpResponse::new(204.into(), vec![], None);
+
+        assert_eq!(String::from(response), "HTTP/1.1 204 No Content\r\n\r\n");
+    }
+
     #[test]
     fn test_http_response_new() {
         let headers = vec!["Content-Type: application/json".into()];
@@ -123,6 +561,59 @@ mod tests {
         assert_eq!(&expected_headers_in_order, response.headers());
     }
 
+    #[test]
+    fn test_http_response_header_keys() {
+        let response = HttpResponse::new(
+            200.into(),
+            vec!["Set-Cookie: a=1".into(), "Set-Cookie: b=2".into()],
+            None,
+        );
+
+        assert_eq!(response.header_keys(), vec!["Set-Cookie", "Set-Cookie"]);
+    }
+
+    #[test]
+    fn test_http_response_set_headers() {
+        let mut response = HttpResponse::new(200.into(), vec!["Server: a".into()], None);
+
+        response.set_headers(vec!["Z-Last: 1".into(), "A-First: 2".into()]);
+
+        assert_eq!(response.header_keys(), vec!["Z-Last", "A-First"]);
+
+        response.clear_headers();
+
+        assert!(response.headers().is_empty());
+    }
+
+    #[test]
+    fn test_http_response_location() {
+        let response =
+            HttpResponse::new(302.into(), vec!["location: /login?next=%2F".into()], None);
+        let location = response.location().unwrap();
+
+        assert!(location.is_relative());
+        assert_eq!(location.path(), "/login");
+
+        let response = HttpResponse::new(
+            301.into(),
+            vec!["Location: https://example.com/moved".into()],
+            None,
+        );
+
+        assert!(response.location().unwrap().is_absolute());
+
+        for headers in [
+            vec![],
+            vec!["Location: ".into()],
+            vec!["Location: http://[::1".into()],
+        ] {
+            assert_eq!(
+                HttpResponse::new(302.into(), headers, None).location(),
+                None
+            );
+        }
+    }
+
     #[test]
     fn test_http_response_get_header() {
         let headers = vec!["Content-Type: application/json".into()];
@@ -165,6 +656,29 @@ mod tests {
     //     assert_eq!(header.value(), "application/json");
     // }
 
+    #[test]
+    fn test_http_response_content_type() {
+        let response = HttpResponse::new(
+            200.into(),
+            vec!["Content-Type: text/html; charset=ISO-8859-1".into()],
+            None,
+        );
+
+        let media_type = response.content_type().unwrap();
+
+        assert_eq!(media_type.essence, "text/html");
+        assert_eq!(media_type.charset, Some("ISO-8859-1".to_string()));
+    }
+
+    #[test]
+    fn test_http_response_should_keep_alive() {
+        let mut response = HttpResponse::new(200.into(), vec![], None);
+        assert!(response.should_keep_alive());
+
+        response.http_version = "HTTP/1.0".into();
+        assert!(!response.should_keep_alive());
+    }
+
     #[test]
     fn test_http_response_get_body() {
         let body = Some("{\"message\": \"Hello, world!\"}");
@@ -179,4 +693,11 @@ mod tests {
         response.set_body(new_body.clone());
         assert_eq!(response.get_body(), &new_body);
     }
+
+    #[test]
+    fn test_http_response_append_body() {
+        let mut response = HttpResponse::new(200.into(), vec![], Some("Hello"));
+        response.append_body(", world!");
+        assert_eq!(response.get_body(), &Some("Hello, world!".to_string()));
+    }
 }
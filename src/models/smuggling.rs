@@ -0,0 +1,181 @@
+use crate::models::HttpHeader;
+
+/// A header pattern that could be used for HTTP request smuggling
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmugglingRisk {
+    pub kind: SmugglingRiskKind,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SmugglingRiskKind {
+    /// Both `Content-Length` and `Transfer-Encoding` are present
+    ContentLengthWithTransferEncoding,
+    /// More than one `Transfer-Encoding` header is present
+    MultipleTransferEncoding,
+    /// A `Transfer-Encoding` header key or value is padded or disguised
+    ObfuscatedTransferEncoding,
+    /// A `Content-Length` value isn't only digits
+    InvalidContentLength,
+}
+
+impl SmugglingRisk {
+    fn new(kind: SmugglingRiskKind, description: String) -> Self {
+        Self { kind, description }
+    }
+}
+
+const KNOWN_TRANSFER_CODINGS: [&str; 5] = ["chunked", "compress", "deflate", "gzip", "identity"];
+
+/// Scan headers for patterns that can make servers disagree on message framing
+///
+/// Header keys are matched ignoring case and surrounding whitespace so padded keys
+/// like `Transfer-Encoding :` are still found.
+pub(crate) fn find_smuggling_risks(headers: &[HttpHeader]) -> Vec<SmugglingRisk> {
+    let mut risks = vec![];
+
+    let content_lengths: Vec<&HttpHeader> = headers
+        .iter()
+        .filter(|header| header.key().trim().eq_ignore_ascii_case("Content-Length"))
+        .collect();
+
+    let transfer_encodings: Vec<&HttpHeader> = headers
+        .iter()
+        .filter(|header| {
+            header
+                .key()
+                .trim()
+                .eq_ignore_ascii_case("Transfer-Encoding")
+        })
+        .collect();
+
+    if !content_lengths.is_empty() && !transfer_encodings.is_empty() {
+        risks.push(SmugglingRisk::new(
+            SmugglingRiskKind::ContentLengthWithTransferEncoding,
+            "Content-Length and Transfer-Encoding are both present".to_string(),
+        ));
+    }
+
+    if transfer_encodings.len() > 1 {
+        risks.push(SmugglingRisk::new(
+            SmugglingRiskKind::MultipleTransferEncoding,
+            format!(
+                "Transfer-Encoding is present {} times",
+                transfer_encodings.len()
+            ),
+        ));
+    }
+
+    for header in transfer_encodings {
+        if header.key() != header.key().trim() {
+            risks.push(SmugglingRisk::new(
+                SmugglingRiskKind::ObfuscatedTransferEncoding,
+                format!("Transfer-Encoding key is padded: {:?}", header.key()),
+            ));
+        }
+
+        let is_padded = header.value() != header.value().trim();
+
+        let has_unknown_coding = header.value().split(',').any(|coding| {
+            let coding = coding.trim_matches([' ', '\t']);
+
+            !KNOWN_TRANSFER_CODINGS
+                .iter()
+                .any(|known| coding.eq_ignore_ascii_case(known))
+        });
+
+        if is_padded || has_unknown_coding {
+            risks.push(SmugglingRisk::new(
+                SmugglingRiskKind::ObfuscatedTransferEncoding,
+                format!(
+                    "Transfer-Encoding value is obfuscated: {:?}",
+                    header.value()
+                ),
+            ));
+        }
+    }
+
+    for header in content_lengths {
+        let value = header.value().trim();
+
+        if value.is_empty() || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+            risks.push(SmugglingRisk::new(
+                SmugglingRiskKind::InvalidContentLength,
+                format!(
+                    "Content-Length value isn't only digits: {:?}",
+                    header.value()
+                ),
+            ));
+        }
+    }
+
+    risks
+}
+
+#[cfg(test)]
+mod smuggling_tests {
+    use super::*;
+
+    fn kinds(headers: &[HttpHeader]) -> Vec<SmugglingRiskKind> {
+        find_smuggling_risks(headers)
+            .into_iter()
+            .map(|risk| risk.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_no_risks() {
+        assert_eq!(kinds(&["Content-Length: 10".into()]), vec![]);
+        assert_eq!(kinds(&["Transfer-Encoding: gzip, Chunked".into()]), vec![]);
+    }
+
+    #[test]
+    fn test_content_length_with_transfer_encoding() {
+        assert_eq!(
+            kinds(&[
+                "Content-Length: 10".into(),
+                "transfer-encoding: chunked".into()
+            ]),
+            vec![SmugglingRiskKind::ContentLengthWithTransferEncoding]
+        );
+    }
+
+    #[test]
+    fn test_multiple_transfer_encoding() {
+        assert_eq!(
+            kinds(&[
+                "Transfer-Encoding: chunked".into(),
+                "Transfer-Encoding: chunked".into()
+            ]),
+            vec![SmugglingRiskKind::MultipleTransferEncoding]
+        );
+    }
+
+    #[test]
+    fn test_obfuscated_transfer_encoding() {
+        assert_eq!(
+            kinds(&[HttpHeader::new("Transfer-Encoding", "chunked ")]),
+            vec![SmugglingRiskKind::ObfuscatedTransferEncoding]
+        );
+        assert_eq!(
+            kinds(&["Transfer-Encoding: xchunked".into()]),
+            vec![SmugglingRiskKind::ObfuscatedTransferEncoding]
+        );
+        assert_eq!(
+            kinds(&[HttpHeader::new("Transfer-Encoding ", "chunked")]),
+            vec![SmugglingRiskKind::ObfuscatedTransferEncoding]
+        );
+    }
+
+    #[test]
+    fn test_invalid_content_length() {
+        assert_eq!(
+            kinds(&["Content-Length: 1e3".into()]),
+            vec![SmugglingRiskKind::InvalidContentLength]
+        );
+        assert_eq!(
+            kinds(&["Content-Length: -1".into()]),
+            vec![SmugglingRiskKind::InvalidContentLength]
+        );
+    }
+}
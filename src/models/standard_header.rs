@@ -0,0 +1,63 @@
+use core::fmt;
+
+/// Well-known header keys for lookups checked at compile time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StandardHeader {
+    Accept,
+    AcceptEncoding,
+    Authorization,
+    CacheControl,
+    Connection,
+    ContentEncoding,
+    ContentLength,
+    ContentType,
+    Cookie,
+    Expect,
+    Host,
+    Location,
+    SetCookie,
+    TransferEncoding,
+    Upgrade,
+    UserAgent,
+}
+
+impl StandardHeader {
+    /// Get the canonical header key
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StandardHeader::Accept => "Accept",
+            StandardHeader::AcceptEncoding => "Accept-Encoding",
+            StandardHeader::Authorization => "Authorization",
+            StandardHeader::CacheControl => "Cache-Control",
+            StandardHeader::Connection => "Connection",
+            StandardHeader::ContentEncoding => "Content-Encoding",
+            StandardHeader::ContentLength => "Content-Length",
+            StandardHeader::ContentType => "Content-Type",
+            StandardHeader::Cookie => "Cookie",
+            StandardHeader::Expect => "Expect",
+            StandardHeader::Host => "Host",
+            StandardHeader::Location => "Location",
+            StandardHeader::SetCookie => "Set-Cookie",
+            StandardHeader::TransferEncoding => "Transfer-Encoding",
+            StandardHeader::Upgrade => "Upgrade",
+            StandardHeader::UserAgent => "User-Agent",
+        }
+    }
+}
+
+impl fmt::Display for StandardHeader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod standard_header_tests {
+    use super::*;
+
+    #[test]
+    fn test_as_str() {
+        assert_eq!(StandardHeader::ContentType.as_str(), "Content-Type");
+        assert_eq!(StandardHeader::UserAgent.to_string(), "User-Agent");
+    }
+}
@@ -1,32 +1,272 @@
 use core::fmt;
 
-use url::Url;
+use url::{Position, Url};
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Uri(Url);
+use crate::error::Error;
+
+/// A parsed uri that keeps the original text it was parsed from
+///
+/// Relative references are resolved against a placeholder base so their path and
+/// query are available, but they have no host or origin.
+///
+/// Equality and display only use the parsed url and whether it's relative.
+#[derive(Debug, Clone)]
+pub struct Uri {
+    url: Url,
+    /// The original text, if parsed from text
+    raw: Option<String>,
+    relative: bool,
+}
+
+impl PartialEq for Uri {
+    fn eq(&self, other: &Self) -> bool {
+        self.url == other.url && self.relative == other.relative
+    }
+}
+
+/// The base relative references are resolved against
+const RELATIVE_BASE: &str = "https://relative.invalid";
 
 impl Uri {
+    /// Create a uri like [Uri::parse], keeping invalid text as a relative path
+    ///
+    /// This never panics. Use [Uri::parse] to reject invalid uris instead.
     pub fn new(uri: &str) -> Self {
-        let uri = if uri.starts_with("https://") || uri.starts_with("http://") {
-            uri
+        Self::parse(uri).unwrap_or_else(|_| {
+            let mut url = Url::parse(RELATIVE_BASE).expect("relative base should be a valid url");
+            url.set_path(uri);
+
+            Self {
+                url,
+                raw: Some(uri.to_string()),
+                relative: true,
+            }
+        })
+    }
+
+    /// Parse a uri, defaulting to `https://` when there is no scheme
+    ///
+    /// Origin-form request targets starting with `/`, like `/path?q=1`, are relative
+    /// references instead. See [Uri::parse_reference].
+    pub fn parse(uri: &str) -> Result<Self, Error> {
+        if uri.starts_with('/') {
+            return Self::parse_reference(uri);
+        }
+
+        let url = if has_http_scheme(uri) {
+            Url::parse(uri)
         } else {
-            &format!("https://{uri}")
+            Url::parse(&format!("https://{uri}"))
         };
 
-        let message = format!("should be a valid url: {uri}");
-        Self(Url::parse(uri).unwrap_or_else(|_| panic!("{}", message)))
+        url.map(|url| Self {
+            url,
+            raw: Some(uri.to_string()),
+            relative: false,
+        })
+        .map_err(|_| Error::InvalidUri {
+            uri: uri.to_string(),
+        })
+    }
+
+    /// Parse an absolute uri or a relative reference like `/path?q=1`
+    ///
+    /// Unlike [Uri::parse] a uri without a scheme is relative instead of defaulting
+    /// to `https://`, as in a `Location` header. A uri starting with `/` is always a
+    /// path, so `//a/b` has the path `//a/b` rather than the host `a`.
+    pub fn parse_reference(uri: &str) -> Result<Self, Error> {
+        let invalid_uri = || Error::InvalidUri {
+            uri: uri.to_string(),
+        };
+
+        match Url::parse(uri) {
+            Ok(url) => Ok(Self {
+                url,
+                raw: Some(uri.to_string()),
+                relative: false,
+            }),
+            Err(url::ParseError::RelativeUrlWithoutBase) => {
+                let url = Url::parse(RELATIVE_BASE)
+                    .and_then(|base| {
+                        if uri.starts_with('/') {
+                            Ok(with_origin_form(base, uri))
+                        } else {
+                            base.join(uri)
+                        }
+                    })
+                    .map_err(|_| invalid_uri())?;
+
+                Ok(Self {
+                    url,
+                    raw: Some(uri.to_string()),
+                    relative: true,
+                })
+            }
+            Err(_) => Err(invalid_uri()),
+        }
+    }
+
+    /// Check if the uri is a relative reference without a scheme or host
+    pub fn is_relative(&self) -> bool {
+        self.relative
+    }
+
+    /// Check if the uri has a scheme
+    pub fn is_absolute(&self) -> bool {
+        !self.relative
+    }
+
+    /// Get the original text the uri was parsed from, if known
+    ///
+    /// Unlike [Uri]'s display this isn't normalized, e.g. no trailing `/` is added.
+    pub fn raw(&self) -> Option<&str> {
+        self.raw.as_deref()
+    }
+
+    /// Get the lowercase scheme, e.g. `https`
+    pub fn scheme(&self) -> &str {
+        self.url.scheme()
+    }
+
+    /// Get the host, if defined
+    pub fn host(&self) -> Option<&str> {
+        if self.is_relative() {
+            return None;
+        }
+
+        self.url.host_str()
+    }
+
+    /// Get the port if it isn't the scheme's default
+    pub fn port(&self) -> Option<u16> {
+        if self.is_relative() {
+            return None;
+        }
+
+        self.url.port()
+    }
+
+    /// Get the port, falling back to the scheme's default like `443` for `https`
+    pub fn port_or_known_default(&self) -> Option<u16> {
+        if self.is_relative() {
+            return None;
+        }
+
+        self.url.port_or_known_default()
+    }
+
+    /// Get the `scheme://host[:port]` prefix without the path or query
+    ///
+    /// The port is omitted when it's the scheme's default. Uris without a host
+    /// have no origin.
+    pub fn origin(&self) -> Option<String> {
+        let host = self.host()?;
+
+        Some(match self.port() {
+            Some(port) => format!("{}://{host}:{port}", self.scheme()),
+            None => format!("{}://{host}", self.scheme()),
+        })
+    }
+
+    /// Get the uri in canonical form for comparison and caching
+    ///
+    /// The scheme and host are lowercased, a default port is removed, and the
+    /// original text is dropped.
+    pub fn canonical(&self) -> Uri {
+        Self {
+            url: self.url.clone(),
+            raw: None,
+            relative: self.relative,
+        }
+    }
+
+    /// Get the uri in canonical form with its query params sorted by key
+    ///
+    /// Params with the same key keep their relative order and aren't re-encoded.
+    pub fn canonical_with_sorted_query(&self) -> Uri {
+        let mut url = self.url.clone();
+
+        if let Some(query) = self.url.query() {
+            let mut params: Vec<&str> = query.split('&').filter(|p| !p.is_empty()).collect();
+            params.sort_by_key(|param| param.split('=').next());
+            url.set_query(Some(&params.join("&")));
+        }
+
+        Self {
+            url,
+            raw: None,
+            relative: self.relative,
+        }
+    }
+
+    /// Get the percent-encoded path, which is at least `/`
+    pub fn path(&self) -> &str {
+        self.url.path()
+    }
+
+    /// Get the raw percent-encoded query without the leading `?`, if defined
+    pub fn query(&self) -> Option<&str> {
+        self.url.query()
+    }
+
+    /// Get the percent-encoded fragment without the leading `#`, if defined
+    pub fn fragment(&self) -> Option<&str> {
+        self.url.fragment()
     }
 }
 
+/// Check if a uri starts with `http://` or `https://`, ignoring ASCII case
+pub(crate) fn has_http_scheme(uri: &str) -> bool {
+    ["https://", "http://"].iter().any(|scheme| {
+        uri.get(..scheme.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    })
+}
+
+/// Set the path, query, and fragment of an origin-form target like `/path?q=1` on url
+///
+/// Unlike [Url::join] this never reads a leading `//` as a host.
+fn with_origin_form(mut url: Url, target: &str) -> Url {
+    let (target, fragment) = match target.split_once('#') {
+        Some((target, fragment)) => (target, Some(fragment)),
+        None => (target, None),
+    };
+
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (target, None),
+    };
+
+    url.set_path(path);
+    url.set_query(query);
+    url.set_fragment(fragment);
+    url
+}
+
 impl Default for Uri {
     fn default() -> Self {
         Self::new("https://example.com")
     }
 }
 
+/// Displays the normalized url, or only its path onwards for relative references
 impl fmt::Display for Uri {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        if self.is_relative() {
+            return write!(f, "{}", &self.url[Position::BeforePath..]);
+        }
+
+        write!(f, "{}", self.url)
+    }
+}
+
+impl From<Url> for Uri {
+    fn from(value: Url) -> Self {
+        Self {
+            url: value,
+            raw: None,
+            relative: false,
+        }
     }
 }
 
@@ -35,3 +275,203 @@ impl From<&str> for Uri {
         Self::new(value)
     }
 }
+
+#[cfg(test)]
+mod uri_tests {
+    use super::*;
+
+    #[test]
+    fn test_host_and_port() {
+        let uri = Uri::new("https://example.com:8443/path");
+        assert_eq!(uri.host(), Some("example.com"));
+        assert_eq!(uri.port(), Some(8443));
+
+        let uri = Uri::new("https://example.com:443/path");
+        assert_eq!(uri.port(), None);
+    }
+
+    #[test]
+    fn test_scheme() {
+        assert_eq!(Uri::new("http://example.com").scheme(), "http");
+        assert_eq!(Uri::new("example.com").scheme(), "https");
+    }
+
+    #[test]
+    fn test_parse_reference() {
+        let uri = Uri::parse_reference("/next?page=2").unwrap();
+        assert!(uri.is_relative());
+        assert_eq!(uri.path(), "/next");
+        assert_eq!(uri.query(), Some("page=2"));
+        assert_eq!(uri.host(), None);
+        assert_eq!(uri.origin(), None);
+        assert_eq!(uri.to_string(), "/next?page=2");
+        assert_ne!(uri, Uri::new("https://relative.invalid/next?page=2"));
+
+        let uri = Uri::parse_reference("https://example.com/next").unwrap();
+        assert!(uri.is_absolute());
+        assert_eq!(uri, Uri::new("https://example.com/next"));
+
+        assert_eq!(
+            Uri::parse_reference("http://[::1"),
+            Err(Error::InvalidUri {
+                uri: "http://[::1".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_port_or_known_default() {
+        assert_eq!(
+            Uri::new("https://example.com").port_or_known_default(),
+            Some(443)
+        );
+        assert_eq!(
+            Uri::new("http://example.com:80").port_or_known_default(),
+            Some(80)
+        );
+        assert_eq!(
+            Uri::new("example.com:8443").port_or_known_default(),
+            Some(8443)
+        );
+        assert_eq!(Uri::new("/path").port_or_known_default(), None);
+    }
+
+    #[test]
+    fn test_origin() {
+        assert_eq!(
+            Uri::new("http://example.com:8080/path?q=1").origin(),
+            Some("http://example.com:8080".to_string())
+        );
+        assert_eq!(
+            Uri::new("https://Example.com:443/path").origin(),
+            Some("https://example.com".to_string())
+        );
+        assert_eq!(
+            Uri::from(Url::parse("data:text/plain,hi").unwrap()).origin(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_path() {
+        assert_eq!(Uri::new("https://example.com").path(), "/");
+        assert_eq!(Uri::new("https://example.com/a%20b?q=1").path(), "/a%20b");
+    }
+
+    #[test]
+    fn test_fragment() {
+        assert_eq!(
+            Uri::new("https://example.com/path?q=1#section-2").fragment(),
+            Some("section-2")
+        );
+        assert_eq!(
+            Uri::parse_reference("/path#a%20b").unwrap().fragment(),
+            Some("a%20b")
+        );
+        assert_eq!(Uri::new("https://example.com/path").fragment(), None);
+    }
+
+    #[test]
+    fn test_raw() {
+        let uri = Uri::new("http://EXAMPLE.com");
+        assert_eq!(uri.raw(), Some("http://EXAMPLE.com"));
+        assert_eq!(uri.to_string(), "http://example.com/");
+        assert_eq!(uri, Uri::new("http://example.com/"));
+
+        let uri = Uri::from(Url::parse("http://EXAMPLE.com").unwrap());
+        assert_eq!(uri.raw(), None);
+        assert_eq!(uri.to_string(), "http://example.com/");
+    }
+
+    #[test]
+    fn test_canonical() {
+        let uri = Uri::new("HTTP://Example.COM:80/").canonical();
+        assert_eq!(uri.to_string(), "http://example.com/");
+        assert_eq!(uri.raw(), None);
+
+        assert_eq!(
+            Uri::new("https://EXAMPLE.com:443").canonical().to_string(),
+            "https://example.com/"
+        );
+        assert_eq!(
+            Uri::new("https://example.com:8443/a?q=1")
+                .canonical()
+                .to_string(),
+            "https://example.com:8443/a?q=1"
+        );
+        assert_eq!(
+            Uri::parse_reference("/a?b=2")
+                .unwrap()
+                .canonical()
+                .to_string(),
+            "/a?b=2"
+        );
+    }
+
+    #[test]
+    fn test_canonical_with_sorted_query() {
+        assert_eq!(
+            Uri::new("https://example.com/?b=2&a=1&b=1&c")
+                .canonical_with_sorted_query()
+                .to_string(),
+            "https://example.com/?a=1&b=2&b=1&c"
+        );
+        assert_eq!(
+            Uri::parse_reference("/x?z=%20&y")
+                .unwrap()
+                .canonical_with_sorted_query()
+                .to_string(),
+            "/x?y&z=%20"
+        );
+        assert_eq!(
+            Uri::new("https://example.com/")
+                .canonical_with_sorted_query()
+                .query(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        let uri = Uri::new("http://[::1");
+        assert!(uri.is_relative());
+        assert_eq!(uri.raw(), Some("http://[::1"));
+        assert_eq!(uri.host(), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Uri::parse("example.com"),
+            Ok(Uri::new("https://example.com"))
+        );
+        assert_eq!(
+            Uri::parse("HTTP://example.com"),
+            Ok(Uri::new("http://example.com"))
+        );
+
+        let uri = Uri::parse("/users?id=1").unwrap();
+        assert!(uri.is_relative());
+        assert_eq!(uri.host(), None);
+        assert_eq!(uri.path(), "/users");
+        assert_eq!(uri.query(), Some("id=1"));
+
+        let uri = Uri::parse("/").unwrap();
+        assert!(uri.is_relative());
+        assert_eq!(uri.to_string(), "/");
+
+        let uri = Uri::parse("//evil.com/path?q=1#top").unwrap();
+        assert!(uri.is_relative());
+        assert_eq!(uri.host(), None);
+        assert_eq!(uri.path(), "//evil.com/path");
+        assert_eq!(uri.query(), Some("q=1"));
+        assert_eq!(uri.fragment(), Some("top"));
+
+        assert_eq!(
+            Uri::parse("http://[::1"),
+            Err(Error::InvalidUri {
+                uri: "http://[::1".to_string()
+            })
+        );
+    }
+}
@@ -8,9 +8,25 @@ impl HttpVersion {
         Self(version.to_string())
     }
 
+    /// Check if the version is an empty string
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     fn is_prefixed(&self) -> bool {
         self.0.starts_with("HTTP/")
     }
+
+    /// Get the numeric major and minor version, if parsable
+    ///
+    /// A version without a minor part like `HTTP/2` has a minor of `0`.
+    pub fn major_minor(&self) -> Option<(u8, u8)> {
+        let version = self.0.strip_prefix("HTTP/").unwrap_or(&self.0);
+
+        let (major, minor) = version.split_once('.').unwrap_or((version, "0"));
+
+        Some((major.parse().ok()?, minor.parse().ok()?))
+    }
 }
 
 impl Default for HttpVersion {
@@ -51,6 +67,14 @@ mod http_version_tests {
         assert!(!version.is_prefixed());
     }
 
+    #[test]
+    fn test_major_minor() {
+        assert_eq!(HttpVersion::from("HTTP/1.1").major_minor(), Some((1, 1)));
+        assert_eq!(HttpVersion::from("1.0").major_minor(), Some((1, 0)));
+        assert_eq!(HttpVersion::from("HTTP/2").major_minor(), Some((2, 0)));
+        assert_eq!(HttpVersion::from("HTTP/x.y").major_minor(), None);
+    }
+
     #[test]
     fn test_default_value() {
         let version = HttpVersion::default();
@@ -0,0 +1,29 @@
+use std::ops::Range;
+
+/// A non-fatal quality issue found while strictly parsing a request
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    pub span: Range<usize>,
+    pub kind: WarningKind,
+    pub message: String,
+}
+
+impl Warning {
+    pub(crate) fn new(span: Range<usize>, kind: WarningKind, message: String) -> Self {
+        Self {
+            span,
+            kind,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WarningKind {
+    /// More than one whitespace character between request line parts
+    ExtraWhitespace,
+    /// A header line continued with leading spaces or tabs
+    ObsoleteLineFolding,
+    /// An http version that isn't uppercase, e.g. `http/1.1`
+    LowercaseVersion,
+}
@@ -2,15 +2,192 @@ use std::ops::Range;
 
 pub type Span = Range<usize>;
 
+/// All the spans of a [crate::models::ParsedHttpRequest]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RequestSpans {
+    pub method: Span,
+    pub uri: Span,
+    pub http_version: Span,
+    pub headers: Vec<Span>,
+    pub body: Option<Span>,
+}
+
+/// All the spans of a [crate::models::PartialHttpRequest]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartialRequestSpans {
+    pub method: Option<Span>,
+    pub uri: Option<Span>,
+    pub http_version: Option<Span>,
+    pub headers: Vec<Span>,
+    pub body: Option<Span>,
+}
+
+/// The byte sizes of each section of a [crate::models::ParsedHttpRequest]
+///
+/// Line sizes include line endings. A byte order mark and the separator line
+/// aren't counted in any section.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SectionSizes {
+    pub request_line: usize,
+    pub headers: usize,
+    pub body: usize,
+}
+
+/// A span as zero based `(line, column)` positions
+///
+/// Columns count characters from the start of the line.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineColSpan {
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+impl LineColSpan {
+    /// Convert a byte span in input to line and column positions
+    pub fn from_span(input: &str, span: &Span) -> Self {
+        Self {
+            start: offset_to_line_col(input, span.start),
+            end: offset_to_line_col(input, span.end),
+        }
+    }
+}
+
+/// Convert a byte offset in input to a zero based `(line, column)` position
+///
+/// Columns count characters from the start of the line. Offsets past the end of
+/// the input are clamped to the end.
+pub fn offset_to_line_col(input: &str, offset: usize) -> (usize, usize) {
+    let bytes = &input.as_bytes()[..offset.min(input.len())];
+
+    let line = bytes.iter().filter(|byte| **byte == b'\n').count();
+
+    let line_start = bytes
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map(|idx| idx + 1)
+        .unwrap_or(0);
+
+    // Count characters by skipping UTF-8 continuation bytes
+    let column = bytes[line_start..]
+        .iter()
+        .filter(|byte| (**byte & 0xC0) != 0x80)
+        .count();
+
+    (line, column)
+}
+
+/// The structural region a line of an HTTP message belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineKind {
+    RequestLine,
+    Header,
+    /// The blank line between the headers and body
+    Separator,
+    Body,
+}
+
+/// Get the byte length of a leading UTF-8 byte order mark, or `0` if there isn't one
+pub fn bom_len(input: &str) -> usize {
+    if input.starts_with('\u{FEFF}') {
+        '\u{FEFF}'.len_utf8()
+    } else {
+        0
+    }
+}
+
+/// Shift a span forward by offset
+pub(crate) fn offset_span(span: Span, offset: usize) -> Span {
+    span.start + offset..span.end + offset
+}
+
+/// Check if a line is an empty `\n` or `\r\n` separator line
+pub(crate) fn is_blank_line(input: &str, line: &Span) -> bool {
+    matches!(&input[line.clone()], "\n" | "\r\n")
+}
+
+/// Get the span of a line without its trailing `\n` or `\r\n`
+pub(crate) fn trim_line_ending(input: &str, line: &Span) -> Span {
+    trim_byte_line_ending(input.as_bytes(), line)
+}
+
+/// Get the span of a line in bytes that may not be UTF-8 without its trailing `\n` or `\r\n`
+pub(crate) fn trim_byte_line_ending(input: &[u8], line: &Span) -> Span {
+    let text = &input[line.clone()];
+    let text = text.strip_suffix(b"\n").unwrap_or(text);
+    let text = text.strip_suffix(b"\r").unwrap_or(text);
+
+    line.start..line.start + text.len()
+}
+
+/// Get the span of the first line without its line ending or a leading byte order mark
+pub(crate) fn first_line_span(input: &str) -> Option<Span> {
+    get_line_spans(input).first().map(|line| {
+        let line = trim_line_ending(input, line);
+
+        bom_len(input).min(line.end)..line.end
+    })
+}
+
+/// Get the span of a header line's key before the colon
+///
+/// Lines without a colon are all key, excluding the line ending.
+pub(crate) fn header_key_span(input: &str, line: &Span) -> Span {
+    header_key_span_with_separator(input, line, ':')
+}
+
+/// Get the span of a header line's key before the separator
+pub(crate) fn header_key_span_with_separator(input: &str, line: &Span, separator: char) -> Span {
+    let line = trim_line_ending(input, line);
+
+    match input[line.clone()].find(separator) {
+        Some(idx) => line.start..line.start + idx,
+        None => line,
+    }
+}
+
+/// Get the span of a header line's value without surrounding whitespace
+///
+/// Empty values return an empty span positioned after the colon and any whitespace.
+pub(crate) fn header_value_span(input: &str, line: &Span) -> Option<Span> {
+    header_value_span_with_separator(input, line, ':')
+}
+
+/// Get the span of a header line's value after the separator without surrounding whitespace
+pub(crate) fn header_value_span_with_separator(
+    input: &str,
+    line: &Span,
+    separator: char,
+) -> Option<Span> {
+    let line = trim_line_ending(input, line);
+    let idx = input[line.clone()].find(separator)?;
+
+    let value_start = line.start + idx + separator.len_utf8();
+    let value = &input[value_start..line.end];
+
+    let start = value_start + (value.len() - value.trim_start_matches([' ', '\t']).len());
+    let end = start + value.trim_matches([' ', '\t']).len();
+
+    Some(start..end)
+}
+
 /// Get all line spans in the given string
 pub fn get_line_spans(input: &str) -> Vec<Range<usize>> {
+    get_byte_line_spans(input.as_bytes())
+}
+
+/// Get all line spans including their `\n` line endings in bytes that may not be UTF-8
+pub(crate) fn get_byte_line_spans(input: &[u8]) -> Vec<Span> {
     let mut spans = Vec::new();
     let mut start = 0;
 
-    for (idx, ch) in input.char_indices() {
-        if ch == '\n' {
-            spans.push(start..idx + ch.len_utf8());
-            start = idx + ch.len_utf8();
+    for (idx, byte) in input.iter().enumerate() {
+        if *byte == b'\n' {
+            spans.push(start..idx + 1);
+            start = idx + 1;
         }
     }
 
@@ -21,6 +198,99 @@ pub fn get_line_spans(input: &str) -> Vec<Range<usize>> {
     spans
 }
 
+#[cfg(test)]
+mod offset_to_line_col_tests {
+    use super::*;
+
+    #[test]
+    fn test_offset_to_line_col() {
+        let text = "GET / HTTP/1.1\nx-kéy: 123\n";
+        assert_eq!(offset_to_line_col(text, 0), (0, 0));
+        assert_eq!(offset_to_line_col(text, 4), (0, 4));
+        assert_eq!(offset_to_line_col(text, 15), (1, 0));
+        assert_eq!(offset_to_line_col(text, 22), (1, 6));
+        assert_eq!(offset_to_line_col(text, 100), (2, 0));
+    }
+
+    #[test]
+    fn test_line_col_span_from_span() {
+        let text = "a\nbc\nd";
+        assert_eq!(
+            LineColSpan::from_span(text, &(3..5)),
+            LineColSpan {
+                start: (1, 1),
+                end: (2, 0)
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod bom_len_tests {
+    use super::*;
+
+    #[test]
+    fn test_bom_len() {
+        assert_eq!(bom_len("\u{FEFF}GET"), 3);
+        assert_eq!(bom_len("GET"), 0);
+        assert_eq!(bom_len(""), 0);
+    }
+}
+
+#[cfg(test)]
+mod header_value_span_tests {
+    use super::*;
+
+    #[test]
+    fn test_header_key_span() {
+        let text = "GET / HTTP/1.1\nx-key: 123\nx-flag\r\n";
+        assert_eq!(header_key_span(text, &(15..26)), 15..20);
+        assert_eq!(header_key_span(text, &(26..34)), 26..32);
+    }
+
+    #[test]
+    fn test_header_value_span() {
+        let text = "GET / HTTP/1.1\nx-key: 123\n";
+        let span = header_value_span(text, &(15..26)).unwrap();
+        assert_eq!(span, 22..25);
+        assert_eq!(&text[span], "123");
+    }
+
+    #[test]
+    fn test_header_value_span_empty_value() {
+        let text = "X-Empty:\r\n";
+        assert_eq!(header_value_span(text, &(0..10)), Some(8..8));
+
+        let text = "X-Empty: \n";
+        assert_eq!(header_value_span(text, &(0..10)), Some(9..9));
+    }
+
+    #[test]
+    fn test_header_value_span_trailing_whitespace() {
+        let text = "X-Key: value  \r\n";
+        assert_eq!(header_value_span(text, &(0..16)), Some(7..12));
+
+        let text = "X-Key:\tvalue \t\t\n";
+        assert_eq!(header_value_span(text, &(0..16)), Some(7..12));
+    }
+
+    #[test]
+    fn test_header_spans_with_separator() {
+        let text = "x-key\t 123 \n";
+        assert_eq!(header_key_span_with_separator(text, &(0..12), '\t'), 0..5);
+        assert_eq!(
+            header_value_span_with_separator(text, &(0..12), '\t'),
+            Some(7..10)
+        );
+        assert_eq!(header_value_span_with_separator(text, &(0..12), '='), None);
+    }
+
+    #[test]
+    fn test_header_value_span_without_colon() {
+        assert_eq!(header_value_span("x-key 123", &(0..9)), None);
+    }
+}
+
 #[cfg(test)]
 mod get_line_spans_tests {
     use super::*;
@@ -0,0 +1,56 @@
+use crate::span::Span;
+
+/// Find all `{{name}}` template variables with the span of each full placeholder
+///
+/// Names are trimmed so `{{ name }}` is the same variable as `{{name}}`. Unmatched
+/// braces and empty placeholders are ignored.
+pub(crate) fn find_template_vars(input: &str) -> Vec<(String, Span)> {
+    let mut vars = vec![];
+    let mut offset = 0;
+
+    while let Some(open) = input[offset..].find("{{").map(|idx| offset + idx) {
+        let Some(close) = input[open + 2..].find("}}").map(|idx| open + 2 + idx) else {
+            break;
+        };
+
+        // Prefer the innermost opening braces, e.g. `{{ {{name}}`
+        let open = input[open..close]
+            .rfind("{{")
+            .map(|idx| open + idx)
+            .unwrap_or(open);
+
+        let name = input[open + 2..close].trim();
+
+        if !name.is_empty() {
+            vars.push((name.to_string(), open..close + 2));
+        }
+
+        offset = close + 2;
+    }
+
+    vars
+}
+
+#[cfg(test)]
+mod find_template_vars_tests {
+    use super::*;
+
+    #[test]
+    fn test_find_template_vars() {
+        let text = "GET {{base_url}}/users HTTP/1.1\nx-key: {{ key }}";
+
+        assert_eq!(
+            find_template_vars(text),
+            vec![("base_url".to_string(), 4..16), ("key".to_string(), 39..48)]
+        );
+    }
+
+    #[test]
+    fn test_find_template_vars_ignores_unmatched_braces() {
+        assert_eq!(
+            find_template_vars("{{ {{a}} }} {{}} {{b"),
+            vec![("a".to_string(), 3..8)]
+        );
+        assert_eq!(find_template_vars("}} {a} {{"), vec![]);
+    }
+}
@@ -5,6 +5,14 @@ use http_message::parse_partial_request;
 use pretty_assertions::assert_eq;
 
 test!(display_empty_request, "./tests/fixtures/empty.request");
+test!(
+    display_get_with_comments_request,
+    "./tests/fixtures/get_with_comments.request"
+);
+test!(
+    display_get_with_empty_header_values_request,
+    "./tests/fixtures/get_with_empty_header_values.request"
+);
 test!(
     display_get_with_headers_request,
     "./tests/fixtures/get_with_headers.request"
@@ -18,14 +26,30 @@ test!(
     "./tests/fixtures/get_without_http_version.request"
 );
 test!(display_get_request, "./tests/fixtures/get.request");
+test!(
+    display_get_with_bom_request,
+    "./tests/fixtures/get_with_bom.request"
+);
 test!(
     display_post_with_body_request,
     "./tests/fixtures/post_with_body.request"
 );
+test!(
+    display_post_with_multiline_body_request,
+    "./tests/fixtures/post_with_multiline_body.request"
+);
+test!(
+    display_post_with_crlf_multiline_body_request,
+    "./tests/fixtures/post_with_crlf_multiline_body.request"
+);
 test!(
     display_post_with_headers_and_body_request,
     "./tests/fixtures/post_with_headers_and_body.request"
 );
+test!(
+    display_uri_only_with_headers_request,
+    "./tests/fixtures/uri_only_with_headers.request"
+);
 test!(
     display_whitespace_request,
     "./tests/fixtures/whitespace.request"
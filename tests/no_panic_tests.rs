@@ -0,0 +1,65 @@
+use http_message::{
+    models::{HttpRequest, HttpResponse, PartialHttpRequest},
+    parse_headers, parse_lenient, parse_partial_request, parse_partial_response, parse_prefix,
+    parse_request, parse_response,
+};
+
+/// Bytes that exercise the parser's line, whitespace, and separator handling
+const ALPHABET: &[u8] = b"GET /HTTP1.:\r\n\t \xef\xbb\xbf\xc2\xa0\xe3\x80\x80\xff{}";
+
+/// Generate deterministic pseudo random inputs from the alphabet
+fn inputs() -> impl Iterator<Item = Vec<u8>> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+
+    (0..20_000).map(move |_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+
+        let len = (state % 48) as usize;
+
+        (0..len)
+            .map(|idx| {
+                let shifted = state.rotate_left(idx as u32 * 5);
+                ALPHABET[(shifted % ALPHABET.len() as u64) as usize]
+            })
+            .collect()
+    })
+}
+
+#[test]
+fn parse_lenient_never_panics() {
+    for input in inputs() {
+        let result = parse_lenient(&input);
+
+        for span in result.spans() {
+            assert!(input.get(span.clone()).is_some(), "{span:?} out of bounds");
+        }
+    }
+}
+
+#[test]
+fn parsers_never_panic() {
+    for input in inputs() {
+        let input = String::from_utf8_lossy(&input);
+
+        if let Ok(partial) = parse_partial_request(&input) {
+            let _ = partial.anomalies();
+            let _: Result<HttpRequest, _> = partial.try_into();
+        }
+
+        if let Ok(partial) = PartialHttpRequest::parse_with_lenient_uri(&input) {
+            let _: Result<HttpRequest, _> = partial.try_into();
+        }
+
+        let _ = parse_request(&input).map(HttpRequest::try_from);
+        let _ = parse_prefix(&input);
+        let _ = parse_headers(&input);
+
+        if let Ok(partial) = parse_partial_response(&input) {
+            let _: Result<HttpResponse, _> = partial.try_into();
+        }
+
+        let _ = parse_response(&input).map(HttpResponse::try_from);
+    }
+}
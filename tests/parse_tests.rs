@@ -1,8 +1,10 @@
+#![allow(clippy::single_range_in_vec_init)]
+
 use http_message::error::Error;
 use http_message::models::HttpRequest;
 use http_message::models::PartialHttpRequest;
 
-use http_message::parse_partial_request;
+use http_message::{parse_partial_request, parse_request};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -54,6 +56,33 @@ fn parse_get_request() {
     );
 }
 
+#[test]
+fn parse_get_with_bom_request() {
+    let content = include_str!("../tests/fixtures/get_with_bom.request");
+
+    let partial = parse_partial_request(content).expect("should be parsable");
+
+    assert_eq!(
+        PartialHttpRequest::parsed(content, Some(3..6), Some(7..26), Some(27..35), vec![], None),
+        partial
+    );
+
+    assert_eq!(Some("GET"), partial.method_str());
+
+    let request: Result<HttpRequest, Error> = partial.try_into();
+
+    assert_eq!(
+        Ok(HttpRequest {
+            uri: "example.com".into(),
+            method: "GET".into(),
+            http_version: "HTTP/1.1".into(),
+            headers: vec![],
+            body: None
+        }),
+        request
+    );
+}
+
 #[test]
 fn parse_get_without_http_version_request() {
     let content = include_str!("../tests/fixtures/get_without_http_version.request");
@@ -178,18 +207,169 @@ fn parse_get_with_multiple_spaces_request() {
 
     let partial = parse_partial_request(content).expect("should be parsable");
 
+    assert_eq!(
+        PartialHttpRequest::parsed(content, Some(0..3), Some(5..24), Some(26..34), vec![], None),
+        partial
+    );
+
+    let request: Result<HttpRequest, Error> = partial.try_into();
+
+    assert_eq!(
+        Ok(HttpRequest {
+            uri: "example.com".into(),
+            method: "GET".into(),
+            http_version: "HTTP/1.1".into(),
+            headers: vec![],
+            body: None
+        }),
+        request
+    );
+}
+
+#[test]
+fn normalize_get_with_multiple_spaces_request() {
+    let content = format!(
+        "{}\n\n",
+        include_str!("../tests/fixtures/get_with_multiple_spaces.request")
+    );
+
+    let parsed = parse_request(&content).expect("should be parsable");
+
+    assert_eq!(
+        "GET https://example.com HTTP/1.1",
+        parsed.normalized_request_line()
+    );
+}
+
+#[test]
+fn parse_uri_only_with_headers_request() {
+    let content = include_str!("../tests/fixtures/uri_only_with_headers.request");
+
+    let partial = parse_partial_request(content).expect("should be parsable");
+
+    assert_eq!(
+        PartialHttpRequest::parsed(content, None, Some(0..19), None, vec![20..31], None),
+        partial
+    );
+
+    assert_eq!(None, partial.method_str());
+    assert_eq!(Some("https://example.com"), partial.uri_str());
+    assert_eq!(Some("x-key: 123\n"), partial.header_str("x-key"));
+
+    let request: Result<HttpRequest, Error> = partial.try_into();
+
+    assert_eq!(Err(Error::missing_required("method")), request);
+}
+
+#[test]
+fn parse_post_with_multiline_body_request() {
+    let content = include_str!("../tests/fixtures/post_with_multiline_body.request");
+
+    let partial = parse_partial_request(content).expect("should be parsable");
+
     assert_eq!(
         PartialHttpRequest::parsed(
             content,
-            Some(0..3),
+            Some(0..4),
             Some(5..24),
-            Some(26..34),
-            vec![],
+            Some(25..33),
+            vec![34..59],
+            Some(60..94)
+        ),
+        partial
+    );
+
+    assert_eq!(
+        Some("first paragraph\n\nsecond paragraph\n"),
+        partial.body_str()
+    );
+}
+
+#[test]
+fn parse_post_with_crlf_multiline_body_request() {
+    let content = include_str!("../tests/fixtures/post_with_crlf_multiline_body.request");
+
+    let partial = parse_partial_request(content).expect("should be parsable");
+
+    assert_eq!(
+        PartialHttpRequest::parsed(
+            content,
+            Some(0..4),
+            Some(5..24),
+            Some(25..33),
+            vec![35..61],
+            Some(63..100)
+        ),
+        partial
+    );
+
+    assert_eq!(
+        Some("first paragraph\r\n\r\nsecond paragraph\r\n"),
+        partial.body_str()
+    );
+
+    let request: Result<HttpRequest, Error> = partial.try_into();
+
+    assert_eq!(
+        Ok(HttpRequest {
+            uri: "example.com".into(),
+            method: "POST".into(),
+            http_version: "HTTP/1.1".into(),
+            headers: vec!["Content-Type: text/plain".into()],
+            body: Some(String::from("first paragraph\r\n\r\nsecond paragraph\r\n"))
+        }),
+        request
+    );
+}
+
+#[test]
+fn parse_get_with_comments_request() {
+    let content = include_str!("../tests/fixtures/get_with_comments.request");
+
+    let partial = parse_partial_request(content).expect("should be parsable");
+
+    assert_eq!(Some("GET"), partial.method_str());
+    assert_eq!(&Some(18..21), partial.method_span());
+    assert_eq!(&Some(22..41), partial.uri_span());
+    assert_eq!(&Some(42..50), partial.http_version_span());
+    assert_eq!(&vec![67..85], partial.header_spans());
+    assert_eq!(&vec![0..18, 51..67], partial.comment_spans());
+
+    let request: Result<HttpRequest, Error> = partial.try_into();
+
+    assert_eq!(
+        Ok(HttpRequest {
+            uri: "example.com".into(),
+            method: "GET".into(),
+            http_version: "HTTP/1.1".into(),
+            headers: vec!["x-api-key: abc123".into()],
+            body: None
+        }),
+        request
+    );
+}
+
+#[test]
+fn parse_get_with_empty_header_values_request() {
+    let content = include_str!("../tests/fixtures/get_with_empty_header_values.request");
+
+    let partial = parse_partial_request(content).expect("should be parsable");
+
+    assert_eq!(
+        PartialHttpRequest::parsed(
+            content,
+            Some(0..3),
+            Some(4..23),
+            Some(24..32),
+            vec![33..42, 42..54],
             None
         ),
         partial
     );
 
+    assert_eq!(Some(("", 41..41)), partial.header_value("X-Trace"));
+    assert_eq!(Some(("", 53..53)), partial.header_value("X-Empty"));
+
     let request: Result<HttpRequest, Error> = partial.try_into();
 
     assert_eq!(
@@ -197,9 +377,67 @@ fn parse_get_with_multiple_spaces_request() {
             uri: "example.com".into(),
             method: "GET".into(),
             http_version: "HTTP/1.1".into(),
-            headers: vec![],
+            headers: vec![("X-Trace", "").into(), ("X-Empty", "").into()],
             body: None
         }),
         request
     );
 }
+
+#[test]
+fn parse_post_with_mixed_line_endings_request() {
+    let content = include_str!("../tests/fixtures/post_with_mixed_line_endings.request");
+
+    let partial = parse_partial_request(content).expect("should be parsable");
+
+    assert_eq!(
+        PartialHttpRequest::parsed(
+            content,
+            Some(0..4),
+            Some(5..24),
+            Some(25..33),
+            vec![34..53, 53..78],
+            Some(80..87)
+        ),
+        partial
+    );
+
+    let parsed = parse_request(content).expect("should be parsable");
+
+    let mut content_spans = vec![
+        parsed.method_span().clone(),
+        parsed.uri_span().clone(),
+        parsed.http_version_span().clone(),
+        parsed.request_line_span(),
+    ];
+
+    for key in parsed.header_keys() {
+        content_spans.push(parsed.header_value_span(key).unwrap());
+    }
+
+    for span in content_spans {
+        assert!(!content[span.clone()].contains('\r'), "{span:?}");
+    }
+
+    assert_eq!(vec!["x-api-key", "content-type"], parsed.header_keys());
+    assert_eq!(
+        Some(("text/plain", 67..77)),
+        parsed.header_value("content-type")
+    );
+
+    let request: Result<HttpRequest, Error> = partial.try_into();
+
+    assert_eq!(
+        Ok(HttpRequest {
+            uri: "https://example.com".into(),
+            method: "POST".into(),
+            http_version: "HTTP/1.1".into(),
+            headers: vec![
+                "x-api-key: abc123".into(),
+                "content-type: text/plain".into()
+            ],
+            body: Some(String::from("hello\r\n"))
+        }),
+        request
+    );
+}
@@ -0,0 +1,52 @@
+use http_message::error::Error;
+use http_message::models::{HttpRequest, SmugglingRiskKind};
+
+use http_message::parse_partial_request;
+use pretty_assertions::assert_eq;
+
+fn risk_kinds(content: &str) -> Vec<SmugglingRiskKind> {
+    let partial = parse_partial_request(content).expect("should be parsable");
+
+    let request: Result<HttpRequest, Error> = partial.try_into();
+
+    request
+        .expect("should be a valid request")
+        .smuggling_risks()
+        .into_iter()
+        .map(|risk| risk.kind)
+        .collect()
+}
+
+#[test]
+fn smuggling_content_length_with_transfer_encoding() {
+    let content = include_str!("../tests/fixtures/smuggling_cl_te.request");
+
+    assert_eq!(
+        vec![SmugglingRiskKind::ContentLengthWithTransferEncoding],
+        risk_kinds(content)
+    );
+}
+
+#[test]
+fn smuggling_obfuscated_transfer_encoding() {
+    let content = include_str!("../tests/fixtures/smuggling_obfuscated_te.request");
+
+    assert_eq!(
+        vec![
+            SmugglingRiskKind::MultipleTransferEncoding,
+            SmugglingRiskKind::ObfuscatedTransferEncoding,
+            SmugglingRiskKind::ObfuscatedTransferEncoding,
+        ],
+        risk_kinds(content)
+    );
+}
+
+#[test]
+fn smuggling_invalid_content_length() {
+    let content = include_str!("../tests/fixtures/smuggling_invalid_cl.request");
+
+    assert_eq!(
+        vec![SmugglingRiskKind::InvalidContentLength],
+        risk_kinds(content)
+    );
+}